@@ -0,0 +1,121 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use serde::Deserialize;
+
+use crate::{source::{workspace_root, Source}, Question};
+
+/// The recorded answers to the questions, as read from `answers.toml`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Answers {
+    #[serde(rename = "answer", default)]
+    entries: Vec<Answer>,
+}
+
+/// A single recorded answer.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Answer {
+    /// The day of the month on which the puzzle was released.
+    pub day: u8,
+    /// The question that was answered, as either 1 or 2.
+    pub part: u8,
+    /// The source data the answer was computed from, either as a path
+    /// relative to the workspace root or as `example`.
+    pub input: String,
+    /// The expected answer, as a string or an integer.
+    pub expected: toml::Value,
+}
+
+impl Answer {
+    /// Returns the expected answer as it would be displayed by a solution.
+    pub fn expected(&self) -> String {
+        match &self.expected {
+            toml::Value::String(answer) => answer.clone(),
+            other => other.to_string(),
+        }
+    }
+
+    /// Returns true if this answer was computed from the given source.
+    fn matches(&self, source: &Source) -> bool {
+        match source {
+            Source::Example => self.input == "example",
+            Source::File(path) if self.input != "example" => {
+                let recorded = workspace_root().join(&self.input);
+                match (recorded.canonicalize(), path.canonicalize()) {
+                    (Ok(recorded), Ok(path)) => recorded == path,
+                    _ => false,
+                }
+            }
+            _ => false,
+        }
+    }
+}
+
+/// The result of checking a computed answer against the recorded answers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    /// The computed answer matches the recorded answer.
+    Pass,
+    /// The computed answer doesn't match the recorded answer.
+    Fail { expected: String, actual: String },
+    /// There is no recorded answer for this question and source.
+    Unrecorded,
+}
+
+impl Check {
+    /// Converts a failed check into an error with a diff of the answers,
+    /// and warns about questions which have no recorded answer.
+    pub fn into_result(self, day: u8, question: Question) -> anyhow::Result<()> {
+        match self {
+            Self::Pass => Ok(()),
+            Self::Fail { expected, actual } => Err(anyhow::anyhow!(
+                "day {} question {} does not match the recorded answer\n- {}\n+ {}",
+                day,
+                question,
+                expected,
+                actual
+            )),
+            Self::Unrecorded => {
+                eprintln!("warning: day {} question {} has no recorded answer", day, question);
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Answers {
+    /// Reads the recorded answers from `answers.toml` in the workspace root.
+    pub fn load() -> anyhow::Result<Self> {
+        let path = answers_path();
+        let source = std::fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        toml::from_str(&source).with_context(|| format!("failed to parse {}", path.display()))
+    }
+
+    /// Returns the recorded answer to the given question, if any.
+    pub fn get(&self, day: u8, question: Question, source: &Source) -> Option<&Answer> {
+        self.entries.iter().find(|answer| {
+            answer.day == day
+                && usize::from(answer.part) == question.index() + 1
+                && answer.matches(source)
+        })
+    }
+
+    /// Checks the given answer against the recorded answer to the given question.
+    pub fn check(&self, day: u8, question: Question, source: &Source, actual: &str) -> Check {
+        match self.get(day, question, source) {
+            Some(answer) if answer.expected() == actual => Check::Pass,
+            Some(answer) => Check::Fail {
+                expected: answer.expected(),
+                actual: actual.to_string(),
+            },
+            None => Check::Unrecorded,
+        }
+    }
+}
+
+/// Returns the path to the recorded answers, which is
+/// `answers.toml` relative to the workspace root.
+pub fn answers_path() -> PathBuf {
+    workspace_root().join("answers.toml")
+}
@@ -0,0 +1,96 @@
+use std::{
+    fmt::Display,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use crate::{Error, Question, Solver};
+
+/// The number of untimed iterations run before any timings are recorded.
+const WARMUP_ITERATIONS: usize = 3;
+
+/// Summary statistics over a series of timed iterations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+}
+
+impl Stats {
+    /// Computes the statistics of the given samples, which must be nonempty.
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort_unstable();
+        let total: Duration = samples.iter().sum();
+
+        Self {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            mean: total / samples.len() as u32,
+        }
+    }
+}
+
+/// The timings of parsing and of each question of a single day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timings {
+    /// The time taken to parse the source data.
+    pub parse: Stats,
+    /// The time taken to answer each question, if it was timed.
+    pub parts: [Option<Stats>; 2],
+}
+
+impl Display for Timings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{:<10}{:>14}{:>14}{:>14}", "", "min", "median", "mean")?;
+
+        let rows = [("parse", Some(self.parse)), ("part one", self.parts[0]), ("part two", self.parts[1])];
+        for (name, stats) in rows {
+            match stats {
+                Some(Stats { min, median, mean }) => {
+                    writeln!(f, "{:<10}{:>14.3?}{:>14.3?}{:>14.3?}", name, min, median, mean)?
+                }
+                None => writeln!(f, "{:<10}{:>14}", name, "skipped")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Runs the given closure for the given number of iterations after
+/// warming up, and returns the statistics of the timed iterations.
+fn time<T>(iterations: usize, mut f: impl FnMut() -> Result<T, Error>) -> Result<Stats, Error> {
+    for _ in 0..WARMUP_ITERATIONS {
+        black_box(f()?);
+    }
+
+    let mut samples = Vec::with_capacity(iterations);
+    for _ in 0..iterations.max(1) {
+        let start = Instant::now();
+        black_box(f()?);
+        samples.push(start.elapsed());
+    }
+
+    Ok(Stats::from_samples(samples))
+}
+
+/// Separately times parsing and answering the given questions with the named
+/// strategy over the given number of iterations, using the source data for
+/// each question.
+pub fn bench<S: Solver>(
+    sources: [&str; 2],
+    iterations: usize,
+    questions: &[Question],
+    strategy: Option<&str>,
+) -> anyhow::Result<Timings> {
+    let parse = time(iterations, || S::parse(sources[0]))?;
+    let mut parts = [None, None];
+
+    for &question in questions {
+        let input = S::parse(sources[question.index()])?;
+        parts[question.index()] = Some(time(iterations, || S::answer(question, &input, strategy))?);
+    }
+
+    Ok(Timings { parse, parts })
+}
@@ -0,0 +1,104 @@
+use std::fmt::Display;
+
+use thiserror::Error;
+
+/// The errors that a [Solver](crate::Solver) can report.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The source data doesn't match the format described by the puzzle.
+    #[error("{0}")]
+    Parse(Box<Diagnostic>),
+    /// The source data was parsed, but the question has no answer for it.
+    #[error("no answer: {0}")]
+    NoAnswer(String),
+}
+
+/// A description of a problem at a particular position in the source data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// The name of the source data, if it is known.
+    pub input: Option<String>,
+    /// The one-based line number of the problem.
+    pub line: usize,
+    /// The one-based column number of the problem, counted in characters.
+    pub column: usize,
+    /// The full text of the offending line.
+    pub snippet: String,
+    /// A description of the problem.
+    pub message: String,
+}
+
+impl Error {
+    /// Constructs a parse error pointing at the start of `remaining`, which
+    /// must be a subslice of `source`; if it isn't, then the error points at
+    /// the end of `source`.
+    pub fn parse(source: &str, remaining: &str, message: impl Into<String>) -> Self {
+        let offset = (remaining.as_ptr() as usize)
+            .checked_sub(source.as_ptr() as usize)
+            .filter(|&offset| offset <= source.len())
+            .unwrap_or(source.len());
+
+        let line_start = source[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[offset..].find('\n').map_or(source.len(), |i| offset + i);
+
+        Self::Parse(Box::new(Diagnostic {
+            input: None,
+            line: source[..offset].matches('\n').count() + 1,
+            column: source[line_start..offset].chars().count() + 1,
+            snippet: source[line_start..line_end].to_string(),
+            message: message.into(),
+        }))
+    }
+
+    /// Converts an error produced by a nom parser over `source` (or over
+    /// a subslice of it) into a parse error.
+    pub fn from_nom(source: &str, err: nom::Err<nom::error::Error<&str>>) -> Self {
+        match err {
+            nom::Err::Error(err) | nom::Err::Failure(err) => {
+                let message = match err.code {
+                    _ if err.input.is_empty() || err.input.starts_with('\n') => {
+                        String::from("unexpected end of line")
+                    }
+                    nom::error::ErrorKind::Eof => String::from("unexpected input"),
+                    nom::error::ErrorKind::TooLarge => String::from("number out of range"),
+                    code => format!("failed to parse ({})", code.description()),
+                };
+
+                Self::parse(source, err.input, message)
+            }
+            nom::Err::Incomplete(_) => Self::parse(source, &source[source.len()..], "unexpected end of input"),
+        }
+    }
+
+    /// Attaches the name of the source data to this error, if it is a parse error.
+    pub fn with_input(self, name: impl Into<String>) -> Self {
+        match self {
+            Self::Parse(mut diagnostic) => {
+                diagnostic.input = Some(name.into());
+                Self::Parse(diagnostic)
+            }
+            other => other,
+        }
+    }
+}
+
+/// Renders the diagnostic in the style of rustc, with a caret under the
+/// offending column.
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+
+        writeln!(f, "{}", self.message)?;
+        writeln!(
+            f,
+            "{}--> {}:{}:{}",
+            gutter,
+            self.input.as_deref().unwrap_or("<input>"),
+            self.line,
+            self.column
+        )?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.snippet)?;
+        write!(f, "{} | {}^", gutter, " ".repeat(self.column - 1))
+    }
+}
@@ -0,0 +1,402 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut, Range},
+};
+
+use nom::{
+    character::complete::none_of,
+    combinator::{opt, recognize},
+};
+
+use crate::{
+    parse::{finish, grid},
+    Error,
+};
+
+/// A position in a [Grid], as a (row, column) pair counted from the top left.
+pub type Position = (usize, usize);
+
+/// Which of the cells around a position count as its neighbours.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// The cells above, below, left and right of the position.
+    Four,
+    /// The cells in [Neighbourhood::Four] along with the diagonal cells.
+    Eight,
+}
+
+impl Neighbourhood {
+    /// The (row, column) offsets of each neighbour from the position.
+    pub fn offsets(self) -> &'static [(isize, isize)] {
+        match self {
+            Neighbourhood::Four => &[(-1, 0), (0, -1), (0, 1), (1, 0)],
+            Neighbourhood::Eight => &[
+                (-1, -1),
+                (-1, 0),
+                (-1, 1),
+                (0, -1),
+                (0, 1),
+                (1, -1),
+                (1, 0),
+                (1, 1),
+            ],
+        }
+    }
+}
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Constructs a grid with every cell set to the given value.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Constructs a grid from its rows, or returns [None] if
+    /// the rows don't all have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+
+        Some(Grid {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Parses a character map into a grid, using the provided function to
+    /// map each character to a cell; trailing empty lines are ignored.
+    pub fn parse(source: &str, cell: impl Fn(char) -> Option<T>) -> Result<Self, Error> {
+        // each character is kept as a slice of the source, to point errors at it
+        let rows = finish(source, opt(grid(recognize(none_of("\n")))))?.unwrap_or_default();
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+
+        for row in &rows {
+            for &character in row {
+                let c = character.chars().next().expect("each cell is one character");
+                let value = cell(c)
+                    .ok_or_else(|| Error::parse(source, character, format!("unexpected character {:?}", c)))?;
+                cells.push(value);
+            }
+
+            if row.len() != width {
+                let message = format!("expected a line of length {}", width);
+                return Err(Error::parse(source, row[0], message));
+            }
+        }
+
+        Ok(Grid { width, height, cells })
+    }
+
+    /// The number of columns in the grid.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The number of rows in the grid.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether the position lies inside the grid.
+    pub fn contains(&self, (row, column): Position) -> bool {
+        row < self.height && column < self.width
+    }
+
+    /// Returns the cell at the position, if it lies inside the grid.
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.contains(position).then(|| &self.cells[self.index_of(position)])
+    }
+
+    /// Returns the cell at the position mutably, if it lies inside the grid.
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        if !self.contains(position) {
+            return None;
+        }
+
+        let index = self.index_of(position);
+        Some(&mut self.cells[index])
+    }
+
+    /// Moves from the position by the given (row, column) offset, and returns
+    /// the new position if it lies inside the grid.
+    pub fn offset(&self, (row, column): Position, (dr, dc): (isize, isize)) -> Option<Position> {
+        let position = (row.checked_add_signed(dr)?, column.checked_add_signed(dc)?);
+        self.contains(position).then_some(position)
+    }
+
+    /// Moves from the position by the given (row, column) offset, wrapping
+    /// around the edges of the grid as if it were a torus.
+    ///
+    /// An empty grid has nowhere to wrap around to, so the position is
+    /// returned unchanged.
+    pub fn wrapping_offset(&self, (row, column): Position, (dr, dc): (isize, isize)) -> Position {
+        if self.width == 0 || self.height == 0 {
+            return (row, column);
+        }
+
+        let wrap = |value: usize, delta: isize, size: usize| {
+            (value as isize + delta).rem_euclid(size as isize) as usize
+        };
+
+        (wrap(row, dr, self.height), wrap(column, dc, self.width))
+    }
+
+    /// Iterates over the neighbours of the position which lie inside the grid.
+    pub fn neighbours(
+        &self,
+        position: Position,
+        neighbourhood: Neighbourhood,
+    ) -> impl Iterator<Item = Position> + '_ {
+        neighbourhood
+            .offsets()
+            .iter()
+            .filter_map(move |&delta| self.offset(position, delta))
+    }
+
+    /// Iterates over the neighbours of the position, wrapping around
+    /// the edges of the grid.
+    pub fn wrapping_neighbours(
+        &self,
+        position: Position,
+        neighbourhood: Neighbourhood,
+    ) -> impl Iterator<Item = Position> + '_ {
+        neighbourhood
+            .offsets()
+            .iter()
+            .map(move |&delta| self.wrapping_offset(position, delta))
+    }
+
+    /// Iterates over every position in the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |column| (row, column)))
+    }
+
+    /// Iterates over every cell in the grid with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Returns the cells in the given row.
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    /// Iterates over the rows of the grid.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks panics on a zero width, but then there's nothing to iterate
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Iterates over the cells in the given column, from top to bottom.
+    pub fn column(&self, column: usize) -> impl Iterator<Item = &T> {
+        assert!(column < self.width, "column {} is out of bounds", column);
+        self.cells.iter().skip(column).step_by(self.width)
+    }
+
+    /// Iterates over the cells, with their positions, in the rectangular region
+    /// spanned by the given rows and columns; the parts of the region which lie
+    /// outside the grid are skipped.
+    pub fn region(
+        &self,
+        rows: Range<usize>,
+        columns: Range<usize>,
+    ) -> impl Iterator<Item = (Position, &T)> {
+        let rows = rows.start.min(self.height)..rows.end.min(self.height);
+        let columns = columns.start.min(self.width)..columns.end.min(self.width);
+
+        rows.flat_map(move |row| {
+            columns
+                .clone()
+                .map(move |column| ((row, column), &self[(row, column)]))
+        })
+    }
+
+    /// Constructs a grid of the same shape by applying the function to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    fn index_of(&self, (row, column): Position) -> usize {
+        row * self.width + column
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &Self::Output {
+        assert!(self.contains(position), "position {:?} is out of bounds", position);
+        &self.cells[self.index_of(position)]
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut Self::Output {
+        assert!(self.contains(position), "position {:?} is out of bounds", position);
+        let index = self.index_of(position);
+        &mut self.cells[index]
+    }
+}
+
+/// Prints the grid one row per line, with no separators between cells; use
+/// [Grid::map] first to choose how each cell is drawn.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 3×4 grid whose cells are numbered row by row.
+    fn numbered() -> Grid<usize> {
+        Grid::from_rows(vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7], vec![8, 9, 10, 11]]).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let grid = Grid::parse("ab\ncd\n\n", Some).unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        assert_eq!(grid.to_string(), "ab\ncd");
+
+        let err = Grid::parse("ab\ncd\ne\n", Some).unwrap_err();
+        assert_eq!(err.to_string().lines().next(), Some("expected a line of length 2"));
+        let Error::Parse(diagnostic) = err else { panic!("expected a parse error") };
+        assert_eq!((diagnostic.line, diagnostic.column), (3, 1));
+
+        let Error::Parse(diagnostic) = Grid::parse("ab\ncX\n", |c| c.is_lowercase().then_some(c)).unwrap_err() else {
+            panic!("expected a parse error")
+        };
+        assert_eq!(diagnostic.message, "unexpected character 'X'");
+        assert_eq!((diagnostic.line, diagnostic.column), (2, 2));
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = numbered();
+        let cells = |position, neighbourhood| {
+            grid.neighbours(position, neighbourhood).map(|position| grid[position]).collect::<Vec<_>>()
+        };
+
+        assert_eq!(cells((0, 0), Neighbourhood::Four), [1, 4]);
+        assert_eq!(cells((0, 0), Neighbourhood::Eight), [1, 4, 5]);
+        assert_eq!(cells((1, 1), Neighbourhood::Four), [1, 4, 6, 9]);
+        assert_eq!(cells((1, 1), Neighbourhood::Eight), [0, 1, 2, 4, 6, 8, 9, 10]);
+        assert_eq!(cells((2, 3), Neighbourhood::Eight), [6, 7, 10]);
+        assert_eq!(cells((1, 3), Neighbourhood::Four), [3, 6, 11]);
+
+        let wrapped = grid
+            .wrapping_neighbours((0, 0), Neighbourhood::Eight)
+            .map(|position| grid[position])
+            .collect::<Vec<_>>();
+        assert_eq!(wrapped, [11, 8, 9, 3, 1, 7, 4, 5]);
+    }
+
+    #[test]
+    fn offsets_match_model() {
+        let grid = numbered();
+        let mut rng = fastrand::Rng::with_seed(9);
+
+        for _ in 0..2000 {
+            let position = (rng.usize(0..3), rng.usize(0..4));
+            let delta = (rng.isize(-10..10), rng.isize(-10..10));
+            let (row, column) = (position.0 as isize + delta.0, position.1 as isize + delta.1);
+
+            let inside = (0..3).contains(&row) && (0..4).contains(&column);
+            let expected = inside.then_some((row as usize, column as usize));
+            assert_eq!(grid.offset(position, delta), expected, "{:?} + {:?}", position, delta);
+
+            let wrapped = (row.rem_euclid(3) as usize, column.rem_euclid(4) as usize);
+            assert_eq!(grid.wrapping_offset(position, delta), wrapped, "{:?} + {:?}", position, delta);
+        }
+
+        assert_eq!(grid.offset((0, 0), (isize::MIN, 0)), None);
+        assert_eq!(grid.offset((usize::MAX, 0), (1, 0)), None);
+    }
+
+    #[test]
+    fn region_is_clipped_to_the_grid() {
+        let grid = numbered();
+        let cells = |rows, columns| grid.region(rows, columns).map(|(_, &cell)| cell).collect::<Vec<_>>();
+
+        assert_eq!(cells(1..3, 1..3), [5, 6, 9, 10]);
+        assert_eq!(cells(2..10, 3..10), [11]);
+        assert_eq!(cells(0..1, 0..usize::MAX), [0, 1, 2, 3]);
+        assert!(cells(3..5, 0..4).is_empty());
+        assert!(cells(0..3, 4..8).is_empty());
+        assert!(cells(Range { start: 2, end: 1 }, 0..4).is_empty());
+
+        let positions = grid.region(1..2, 2..9).map(|(position, _)| position).collect::<Vec<_>>();
+        assert_eq!(positions, [(1, 2), (1, 3)]);
+    }
+
+    #[test]
+    fn empty_grid() {
+        let mut grid = Grid::parse("", Some).unwrap();
+        assert_eq!(grid, Grid::from_rows(Vec::new()).unwrap());
+        assert_eq!((grid.width(), grid.height()), (0, 0));
+
+        assert!(!grid.contains((0, 0)));
+        assert_eq!(grid.get((0, 0)), None);
+        assert_eq!(grid.get_mut((0, 0)), None);
+        assert_eq!(grid.offset((0, 0), (0, 0)), None);
+        assert_eq!(grid.wrapping_offset((2, 5), (-1, 1)), (2, 5));
+        assert_eq!(grid.neighbours((0, 0), Neighbourhood::Eight).count(), 0);
+        assert_eq!(grid.positions().count(), 0);
+        assert_eq!(grid.rows().count(), 0);
+        assert_eq!(grid.region(0..5, 0..5).count(), 0);
+        assert_eq!(grid.to_string(), "");
+
+        // a grid of empty rows has a height but no cells
+        let grid = Grid::<u8>::new(0, 3, 0);
+        assert_eq!(grid.get((1, 0)), None);
+        assert_eq!(grid.wrapping_offset((1, 0), (1, 1)), (1, 0));
+    }
+
+    #[test]
+    fn get_mut_is_bounds_checked() {
+        let mut grid = numbered();
+        // (0, 5) would be index 5, which is inside the cells but not the grid
+        assert_eq!(grid.get_mut((0, 5)), None);
+        *grid.get_mut((2, 1)).unwrap() = 99;
+        assert_eq!(grid[(2, 1)], 99);
+    }
+}
@@ -0,0 +1,464 @@
+use std::{fmt::Debug, ops::Range};
+
+/// An integer type which can be used as the endpoint of an interval.
+///
+/// Offsets between endpoints are computed in [i128], which can represent
+/// the difference between any two values of the implementing types.
+pub trait Endpoint: Copy + Ord + Debug {
+    /// Converts the value to an [i128] without loss.
+    fn widen(self) -> i128;
+    /// Converts the value back from an [i128], if it is representable.
+    fn narrow(value: i128) -> Option<Self>;
+}
+
+macro_rules! impl_endpoint {
+    ($($t:ty),*) => {
+        $(
+            impl Endpoint for $t {
+                fn widen(self) -> i128 {
+                    self as i128
+                }
+
+                fn narrow(value: i128) -> Option<Self> {
+                    Self::try_from(value).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_endpoint!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// Moves the value by the offset, if the result is representable.
+fn shift<T: Endpoint>(value: T, offset: i128) -> Option<T> {
+    T::narrow(value.widen().checked_add(offset)?)
+}
+
+/// A set of integers, stored as a sorted list of disjoint half-open
+/// ranges, none of which are empty or touch one another.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: Endpoint> IntervalSet<T> {
+    /// Constructs an empty set.
+    pub fn new() -> Self {
+        IntervalSet { ranges: Vec::new() }
+    }
+
+    /// Constructs a set from any collection of ranges, merging any that overlap.
+    fn normalize(mut ranges: Vec<Range<T>>) -> Self {
+        ranges.retain(|range| range.start < range.end);
+        ranges.sort_unstable_by_key(|range| range.start);
+
+        let mut merged: Vec<Range<T>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+
+        IntervalSet { ranges: merged }
+    }
+
+    /// Adds every value in the range to the set.
+    pub fn insert(&mut self, range: Range<T>) {
+        let mut ranges = std::mem::take(&mut self.ranges);
+        ranges.push(range);
+        *self = Self::normalize(ranges);
+    }
+
+    /// The disjoint ranges which make up the set, in ascending order.
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    /// Whether the set has no values.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Whether the value is in the set.
+    pub fn contains(&self, value: T) -> bool {
+        let index = self.ranges.partition_point(|range| range.end <= value);
+        self.ranges.get(index).is_some_and(|range| range.start <= value)
+    }
+
+    /// The least value in the set, if there is one.
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|range| range.start)
+    }
+
+    /// Returns the values which are in either set.
+    pub fn union(&self, other: &Self) -> Self {
+        Self::normalize(self.ranges.iter().chain(&other.ranges).cloned().collect())
+    }
+
+    /// Returns the values which are in both sets.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+
+        while let (Some(a), Some(b)) = (self.ranges.get(i), other.ranges.get(j)) {
+            let start = a.start.max(b.start);
+            let end = a.end.min(b.end);
+            if start < end {
+                ranges.push(start..end);
+            }
+
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        IntervalSet { ranges }
+    }
+
+    /// Returns the values which are in this set but not in the other.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let mut j = 0;
+
+        for range in &self.ranges {
+            let mut cursor = range.start;
+
+            // skip everything which ends before this range starts
+            while other.ranges.get(j).is_some_and(|b| b.end <= cursor) {
+                j += 1;
+            }
+
+            let mut k = j;
+            while let Some(b) = other.ranges.get(k).filter(|b| b.start < range.end) {
+                if cursor < b.start {
+                    ranges.push(cursor..b.start);
+                }
+                cursor = cursor.max(b.end);
+                k += 1;
+            }
+
+            if cursor < range.end {
+                ranges.push(cursor..range.end);
+            }
+        }
+
+        IntervalSet { ranges }
+    }
+}
+
+impl<T: Endpoint> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Endpoint> From<Range<T>> for IntervalSet<T> {
+    fn from(range: Range<T>) -> Self {
+        Self::normalize(vec![range])
+    }
+}
+
+impl<T: Endpoint> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        Self::normalize(iter.into_iter().collect())
+    }
+}
+
+/// A map which moves each of a set of disjoint ranges by a fixed offset,
+/// and leaves every other value where it is.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OffsetMap<T> {
+    /// The domain of each piece, with the offset applied to it; sorted by
+    /// domain, none of which are empty or overlap one another.
+    pieces: Vec<(Range<T>, i128)>,
+}
+
+impl<T: Endpoint> OffsetMap<T> {
+    /// Constructs the identity map.
+    pub fn new() -> Self {
+        OffsetMap { pieces: Vec::new() }
+    }
+
+    /// Maps the `domain` onto the range of the same length starting at
+    /// `target`, replacing any mapping for the values in `domain`.
+    ///
+    /// Returns false, leaving the map unchanged, if the end of the
+    /// target range isn't representable; an empty domain is ignored.
+    #[must_use]
+    pub fn insert(&mut self, domain: Range<T>, target: T) -> bool {
+        if domain.start >= domain.end {
+            return true;
+        }
+
+        let offset = target.widen() - domain.start.widen();
+        if shift(domain.end, offset).is_none() {
+            return false;
+        }
+
+        let mut pieces = Vec::with_capacity(self.pieces.len() + 2);
+        for (range, offset) in self.pieces.drain(..) {
+            if range.end <= domain.start || domain.end <= range.start {
+                pieces.push((range, offset));
+                continue;
+            }
+
+            if range.start < domain.start {
+                pieces.push((range.start..domain.start, offset));
+            }
+            if domain.end < range.end {
+                pieces.push((domain.end..range.end, offset));
+            }
+        }
+
+        pieces.push((domain, offset));
+        pieces.sort_unstable_by_key(|(range, _)| range.start);
+        self.pieces = pieces;
+
+        true
+    }
+
+    /// Iterates over the pieces of the map as (domain, image) pairs, in
+    /// ascending order of domain.
+    pub fn pieces(&self) -> impl Iterator<Item = (Range<T>, Range<T>)> + '_ {
+        self.pieces
+            .iter()
+            .map(|(range, offset)| (range.clone(), self.image_of(range, *offset)))
+    }
+
+    /// The values which are moved by some piece of the map.
+    pub fn domain(&self) -> IntervalSet<T> {
+        self.pieces.iter().map(|(range, _)| range.clone()).collect()
+    }
+
+    /// Returns the image of a single value.
+    pub fn apply(&self, value: T) -> T {
+        let index = self.pieces.partition_point(|(range, _)| range.end <= value);
+        match self.pieces.get(index) {
+            Some((range, offset)) if range.start <= value => {
+                shift(value, *offset).expect("pieces are checked to be representable on insert")
+            }
+            _ => value,
+        }
+    }
+
+    /// Splits the range into the parts which fall into each piece of the
+    /// map and the gaps between them, each with the offset applied to it
+    /// (which is 0 for the gaps), in ascending order.
+    fn split(&self, range: Range<T>) -> Vec<(Range<T>, i128)> {
+        let mut parts = Vec::new();
+        let mut cursor = range.start;
+        let first = self.pieces.partition_point(|(piece, _)| piece.end <= range.start);
+
+        for (piece, offset) in self.pieces[first..].iter().take_while(|(piece, _)| piece.start < range.end) {
+            if cursor < piece.start {
+                parts.push((cursor..piece.start, 0));
+            }
+
+            let end = range.end.min(piece.end);
+            parts.push((cursor.max(piece.start)..end, *offset));
+            cursor = end;
+        }
+
+        if cursor < range.end {
+            parts.push((cursor..range.end, 0));
+        }
+
+        parts
+    }
+
+    /// Returns the image of every value in the set.
+    pub fn image(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        set.ranges()
+            .iter()
+            .flat_map(|range| self.split(range.clone()))
+            .map(|(part, offset)| self.image_of(&part, offset))
+            .collect()
+    }
+
+    /// Returns the map which applies this map and then the other, such
+    /// that `self.then(other).apply(x) == other.apply(self.apply(x))`.
+    pub fn then(&self, other: &Self) -> Self {
+        let mut pieces = Vec::with_capacity(self.pieces.len() + other.pieces.len());
+
+        // within a piece of this map, follow its image through the other map
+        for (range, offset) in &self.pieces {
+            let image = self.image_of(range, *offset);
+            for (part, next) in other.split(image) {
+                pieces.push((self.image_of(&part, -offset), offset + next));
+            }
+        }
+
+        // everywhere else, this map is the identity, so only the other map applies
+        let domain = self.domain();
+        for (range, offset) in &other.pieces {
+            let outside = IntervalSet::from(range.clone()).difference(&domain);
+            pieces.extend(outside.ranges.into_iter().map(|part| (part, *offset)));
+        }
+
+        pieces.retain(|(_, offset)| *offset != 0);
+        pieces.sort_unstable_by_key(|(range, _)| range.start);
+
+        // merge neighbouring pieces which move by the same offset
+        let mut merged: Vec<(Range<T>, i128)> = Vec::with_capacity(pieces.len());
+        for (range, offset) in pieces {
+            match merged.last_mut() {
+                Some((last, last_offset)) if last.end == range.start && *last_offset == offset => {
+                    last.end = range.end
+                }
+                _ => merged.push((range, offset)),
+            }
+        }
+
+        OffsetMap { pieces: merged }
+    }
+
+    /// Returns every value whose image is in the set.
+    pub fn preimage(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        // values outside every piece are their own preimages
+        let mut preimage = set.difference(&self.domain());
+
+        for (range, offset) in &self.pieces {
+            let image = IntervalSet::from(self.image_of(range, *offset));
+            // everything in the image of a piece moves back into its domain
+            let hits = set
+                .intersection(&image)
+                .ranges()
+                .iter()
+                .map(|hit| self.image_of(hit, -offset))
+                .collect::<IntervalSet<_>>();
+            preimage = preimage.union(&hits);
+        }
+
+        preimage
+    }
+
+    /// Applies the offset to a range inside the domain of a piece, or (with
+    /// the offset negated) to a range inside the image of a piece.
+    fn image_of(&self, range: &Range<T>, offset: i128) -> Range<T> {
+        let start = shift(range.start, offset).expect("pieces are checked to be representable on insert");
+        let end = shift(range.end, offset).expect("pieces are checked to be representable on insert");
+        start..end
+    }
+}
+
+impl<T: Endpoint> Default for OffsetMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    /// Every value which the random sets and maps can touch.
+    const UNIVERSE: Range<u64> = 0..160;
+
+    fn random_range(rng: &mut fastrand::Rng) -> Range<u64> {
+        // allow empty and reversed ranges, which should be ignored
+        rng.u64(0..64)..rng.u64(0..64)
+    }
+
+    /// A random set of small values, along with the same set as a [BTreeSet].
+    fn random_set(rng: &mut fastrand::Rng) -> (IntervalSet<u64>, BTreeSet<u64>) {
+        let ranges = (0..rng.usize(0..5)).map(|_| random_range(rng)).collect::<Vec<_>>();
+        let model = ranges.iter().flat_map(|range| range.clone()).collect();
+        (ranges.into_iter().collect(), model)
+    }
+
+    /// A random map which moves a few small ranges onto other small ranges.
+    fn random_map(rng: &mut fastrand::Rng) -> OffsetMap<u64> {
+        let mut map = OffsetMap::new();
+        for _ in 0..rng.usize(0..5) {
+            assert!(map.insert(random_range(rng), rng.u64(0..64)));
+        }
+        map
+    }
+
+    fn to_model(set: &IntervalSet<u64>) -> BTreeSet<u64> {
+        set.ranges().iter().flat_map(|range| range.clone()).collect()
+    }
+
+    /// Checks that the ranges are sorted, non-empty, and neither overlap
+    /// nor touch one another.
+    fn assert_normalized(set: &IntervalSet<u64>) {
+        assert!(set.ranges().iter().all(|range| range.start < range.end));
+        assert!(set.ranges().windows(2).all(|pair| pair[0].end < pair[1].start));
+    }
+
+    #[test]
+    fn set_operations_match_model() {
+        let mut rng = fastrand::Rng::with_seed(10);
+
+        for _ in 0..2000 {
+            let (a, a_model) = random_set(&mut rng);
+            let (b, b_model) = random_set(&mut rng);
+
+            for (set, model) in [
+                (a.union(&b), a_model.union(&b_model).copied().collect::<BTreeSet<_>>()),
+                (a.intersection(&b), a_model.intersection(&b_model).copied().collect()),
+                (a.difference(&b), a_model.difference(&b_model).copied().collect()),
+            ] {
+                assert_normalized(&set);
+                assert_eq!(to_model(&set), model, "a = {:?}, b = {:?}", a, b);
+            }
+
+            assert_eq!(a.min(), a_model.first().copied());
+            assert_eq!(a.is_empty(), a_model.is_empty());
+            assert!(UNIVERSE.into_iter().all(|value| a.contains(value) == a_model.contains(&value)));
+        }
+    }
+
+    #[test]
+    fn then_applies_both_maps() {
+        let mut rng = fastrand::Rng::with_seed(13);
+
+        for _ in 0..2000 {
+            let (a, b) = (random_map(&mut rng), random_map(&mut rng));
+            let composed = a.then(&b);
+
+            for value in UNIVERSE {
+                assert_eq!(composed.apply(value), b.apply(a.apply(value)), "a = {:?}, b = {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn image_and_preimage_match_model() {
+        let mut rng = fastrand::Rng::with_seed(14);
+
+        for _ in 0..2000 {
+            let map = random_map(&mut rng);
+            let (set, model) = random_set(&mut rng);
+
+            let image = map.image(&set);
+            assert_normalized(&image);
+            assert_eq!(to_model(&image), model.iter().map(|&value| map.apply(value)).collect());
+
+            let preimage = map.preimage(&set);
+            assert_normalized(&preimage);
+            let expected = UNIVERSE.filter(|&value| model.contains(&map.apply(value))).collect();
+            assert_eq!(to_model(&preimage), expected, "map = {:?}, set = {:?}", map, set);
+
+            // every value lands back in the preimage of its image, and the
+            // image of a preimage lands back in the set
+            assert!(to_model(&map.preimage(&image)).is_superset(&model));
+            assert!(to_model(&map.image(&preimage)).is_subset(&model));
+        }
+    }
+
+    #[test]
+    fn insert_rejects_unrepresentable_targets() {
+        let mut map = OffsetMap::<u8>::new();
+        assert!(!map.insert(0..10, 250));
+        assert_eq!(map, OffsetMap::new());
+        assert!(map.insert(0..10, 245));
+        assert_eq!(map.apply(9), 254);
+        assert!(map.insert(Range { start: 20, end: 10 }, 0));
+    }
+}
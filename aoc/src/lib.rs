@@ -1,16 +1,33 @@
+use answers::Answers;
 use argh::FromArgs;
-use std::{str::FromStr, fmt::Display};
+use bench::Timings;
+pub use error::Error;
+use output::{Format, Outcome};
+use source::Source;
+use anyhow::Context;
+use std::{str::FromStr, fmt::Display, path::{Path, PathBuf}, time::Instant};
 use thiserror::Error;
 
+pub mod answers;
+pub mod bench;
+pub mod error;
+pub mod grid;
+pub mod interval;
+pub mod math;
+pub mod output;
+pub mod parse;
+pub mod source;
+
 #[derive(Error, Debug)]
 pub struct QuestionParseError(String);
 
 impl Display for QuestionParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Expected --question [0|1] or -q [0|1]; got {}", self.0)
+        write!(f, "Expected --question [1|2] or -q [1|2]; got {}", self.0)
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Question {
     One,
     Two,
@@ -23,7 +40,26 @@ impl FromStr for Question {
         match s {
             "1" => Ok(Self::One),
             "2" => Ok(Self::Two),
-            other @ _ => Err(QuestionParseError(String::from(other))),
+            other => Err(QuestionParseError(String::from(other))),
+        }
+    }
+}
+
+impl Question {
+    /// Returns the zero-based index of the question.
+    pub fn index(self) -> usize {
+        match self {
+            Self::One => 0,
+            Self::Two => 1,
+        }
+    }
+}
+
+impl Display for Question {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::One => write!(f, "1"),
+            Self::Two => write!(f, "2"),
         }
     }
 }
@@ -33,21 +69,288 @@ impl FromStr for Question {
 pub struct Solution {
     #[argh(option, short = 'q')]
     /// the question to run
-    pub question: Question, 
+    pub question: Option<Question>,
+    #[argh(option, short = 'i')]
+    /// read the source data from the given path (or from stdin if
+    /// the path is -) instead of from data/dayN.txt
+    pub input: Option<PathBuf>,
+    #[argh(switch, short = 'e')]
+    /// use the example input from the puzzle description
+    pub example: bool,
+    #[argh(switch)]
+    /// check the answer against the answers recorded in answers.toml
+    pub check: bool,
+    #[argh(option)]
+    /// time parsing and both questions over the given number of iterations
+    pub bench: Option<usize>,
+    #[argh(option, short = 'o', default = "Format::Text")]
+    /// print the answer as text (the default) or as a line of json
+    pub output: Format,
+    #[argh(option)]
+    /// answer with the given strategy, for days which have more than one
+    pub strategy: Option<String>,
+    #[argh(switch)]
+    /// print how the answer was reached to stderr, for days which
+    /// can explain it
+    pub explain: bool,
+    #[argh(option)]
+    /// write the input as a Graphviz graph to the given path, for days
+    /// which can draw it
+    pub dot: Option<PathBuf>,
 }
 
 impl Solution {
     pub fn new() -> Self {
         argh::from_env()
     }
+
+    /// Reads the source data from the selected source and prints
+    /// the answer to the selected question.
+    pub fn run<S: Solver>(&self) -> anyhow::Result<()> {
+        let day = Day::of::<S>();
+        let source = Source::from_args(self.input.as_deref(), self.example, day.number)?;
+
+        if let Some(iterations) = self.bench {
+            if self.explain {
+                anyhow::bail!("--explain can't be used with --bench");
+            }
+            if self.dot.is_some() {
+                anyhow::bail!("--dot can't be used with --bench");
+            }
+            let [one, two] = source.read_all(&day)?;
+            let questions = [Question::One, Question::Two];
+            day.check_strategy(self.strategy.as_deref())?;
+            print!("{}", (day.bench)([&one, &two], iterations, &questions, self.strategy.as_deref())?);
+            return Ok(());
+        }
+
+        let question = self
+            .question
+            .ok_or_else(|| anyhow::anyhow!("Expected --question [1|2] or -q [1|2], or --bench"))?;
+        if self.explain {
+            eprintln!("{}", day.explanation(question, &source)?);
+        }
+        if let Some(path) = &self.dot {
+            day.write_dot(question, &source, path)?;
+        }
+
+        let outcome = day.run(question, &source, self.strategy.as_deref())?;
+        println!("{}", outcome.render(self.output));
+
+        if self.check {
+            Answers::load()?
+                .check(day.number, question, &source, &outcome.answer)
+                .into_result(day.number, question)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for Solution {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A solution to both questions of a single day.
+///
+/// The source data is parsed exactly once into [Solver::Input],
+/// and then each question is answered from a shared reference
+/// to it, so neither question needs to know where the source
+/// data came from.
+pub trait Solver {
+    /// The day of the month on which the puzzle was released.
+    const DAY: u8;
+
+    /// The example inputs from the puzzle description, for each question.
+    const EXAMPLES: [&'static str; 2];
+
+    /// The parsed representation of the source data.
+    type Input;
+
+    /// The type of the answers to both questions.
+    type Answer: Display;
+
+    /// The names of the strategies the solver can answer with, which are
+    /// chosen with `--strategy`, and the first of which is the default.
+    /// Most solvers only have one way of answering, and leave this empty.
+    const STRATEGIES: &'static [&'static str] = &[];
+
+    /// Parses the source data into the input shared by both questions.
+    fn parse(source: &str) -> Result<Self::Input, Error>;
+
+    /// Computes the answer to question 1.
+    fn part_one(input: &Self::Input) -> Result<Self::Answer, Error>;
+
+    /// Computes the answer to question 2.
+    fn part_two(input: &Self::Input) -> Result<Self::Answer, Error>;
+
+    /// Computes the answer to the question with the named strategy, which
+    /// is one of [Solver::STRATEGIES], or with the default if it's [None].
+    ///
+    /// Only solvers with more than one strategy need to override this.
+    fn answer(question: Question, input: &Self::Input, strategy: Option<&str>) -> Result<Self::Answer, Error> {
+        let _ = strategy;
+
+        match question {
+            Question::One => Self::part_one(input),
+            Question::Two => Self::part_two(input),
+        }
+    }
+
+    /// Describes how the answer to the question is reached from the input,
+    /// in enough detail to audit it, which is printed with `--explain`.
+    ///
+    /// Returns [None] for solvers which have nothing to explain, which is
+    /// the default.
+    fn explain(question: Question, input: &Self::Input) -> Result<Option<String>, Error> {
+        let _ = (question, input);
+
+        Ok(None)
+    }
+
+    /// Draws the input as a Graphviz graph, highlighting what matters to
+    /// the question, which is written to a file with `--dot`.
+    ///
+    /// Returns [None] for solvers which have nothing to draw, which is
+    /// the default.
+    fn dot(question: Question, input: &Self::Input) -> Result<Option<String>, Error> {
+        let _ = (question, input);
+
+        Ok(None)
+    }
+}
+
+/// Parses the source data and computes the answer to the given question
+/// with the named strategy.
+pub fn solve<S: Solver>(question: Question, source: &str, strategy: Option<&str>) -> Result<Outcome, Error> {
+    let start = Instant::now();
+    let input = S::parse(source)?;
+    let parse = start.elapsed();
+
+    let start = Instant::now();
+    let answer = S::answer(question, &input, strategy)?;
+    let solve = start.elapsed();
+
+    Ok(Outcome {
+        day: S::DAY,
+        question,
+        answer: answer.to_string(),
+        parse,
+        solve,
+    })
+}
+
+/// Parses the source data and explains how the answer to the given
+/// question is reached, if the solver can explain it.
+pub fn explain<S: Solver>(question: Question, source: &str) -> Result<Option<String>, Error> {
+    S::explain(question, &S::parse(source)?)
+}
+
+/// Parses the source data and draws it as a Graphviz graph for the given
+/// question, if the solver can draw it.
+pub fn dot<S: Solver>(question: Question, source: &str) -> Result<Option<String>, Error> {
+    S::dot(question, &S::parse(source)?)
+}
+
+/// Computes the answer to a question from the source data with the named strategy.
+pub type SolveFn = fn(Question, &str, Option<&str>) -> Result<Outcome, Error>;
+
+/// Times parsing and answering the given questions from the source data for
+/// each question with the named strategy, over the given number of iterations.
+pub type BenchFn = fn([&str; 2], usize, &[Question], Option<&str>) -> anyhow::Result<Timings>;
+
+/// Explains how the answer to a question is reached from the source data.
+pub type ExplainFn = fn(Question, &str) -> Result<Option<String>, Error>;
+
+/// Draws the source data as a Graphviz graph for a question.
+pub type DotFn = fn(Question, &str) -> Result<Option<String>, Error>;
+
+/// The type-erased entrypoint of a single day, which the workspace
+/// runner uses to dispatch to each solution.
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+    /// The day of the month on which the puzzle was released.
+    pub number: u8,
+    /// The example inputs from the puzzle description, for each question.
+    pub examples: [&'static str; 2],
+    /// The names of the strategies the day can answer with, if it has more than one.
+    pub strategies: &'static [&'static str],
+    /// Computes the answer to the given question from the source data
+    /// with the named strategy.
+    pub solve: SolveFn,
+    /// Times parsing and answering the given questions from the source
+    /// data for each question, over the given number of iterations.
+    pub bench: BenchFn,
+    /// Explains how the answer to the given question is reached from the
+    /// source data, if the day can explain it.
+    pub explain: ExplainFn,
+    /// Draws the source data as a Graphviz graph for the given question,
+    /// if the day can draw it.
+    pub dot: DotFn,
+}
+
+impl Day {
+    /// Erases the type of the given solver.
+    pub const fn of<S: Solver>() -> Self {
+        Self {
+            number: S::DAY,
+            examples: S::EXAMPLES,
+            strategies: S::STRATEGIES,
+            solve: solve::<S>,
+            bench: bench::bench::<S>,
+            explain: explain::<S>,
+            dot: dot::<S>,
+        }
+    }
+
+    /// Checks that the named strategy is one that this day can answer with.
+    pub fn check_strategy(&self, strategy: Option<&str>) -> anyhow::Result<()> {
+        match strategy {
+            Some(name) if !self.strategies.contains(&name) => match self.strategies {
+                [] => anyhow::bail!("day {} has no strategies to choose from", self.number),
+                strategies => anyhow::bail!(
+                    "day {} has no strategy {:?}; expected one of: {}",
+                    self.number,
+                    name,
+                    strategies.join(", ")
+                ),
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Reads the source data for the given question from the given source,
+    /// and computes the answer to the question with the named strategy.
+    pub fn run(&self, question: Question, source: &Source, strategy: Option<&str>) -> anyhow::Result<Outcome> {
+        self.check_strategy(strategy)?;
+        let text = source.read(self, question)?;
+        Ok((self.solve)(question, &text, strategy).map_err(|err| err.with_input(source.name()))?)
+    }
+
+    /// Reads the source data for the given question from the given source,
+    /// and explains how the answer to the question is reached.
+    pub fn explanation(&self, question: Question, source: &Source) -> anyhow::Result<String> {
+        let text = source.read(self, question)?;
+        (self.explain)(question, &text)
+            .map_err(|err| err.with_input(source.name()))?
+            .ok_or_else(|| anyhow::anyhow!("day {} has nothing to explain", self.number))
+    }
+
+    /// Reads the source data for the given question from the given source,
+    /// and writes it as a Graphviz graph for the question to the given path.
+    pub fn write_dot(&self, question: Question, source: &Source, path: &Path) -> anyhow::Result<()> {
+        let text = source.read(self, question)?;
+        let graph = (self.dot)(question, &text)
+            .map_err(|err| err.with_input(source.name()))?
+            .ok_or_else(|| anyhow::anyhow!("day {} has nothing to draw", self.number))?;
+        std::fs::write(path, graph).with_context(|| format!("failed to write {}", path.display()))
+    }
 }
 
 pub fn read_stdin_to_string() -> String {
-    std::io::stdin()
-        .lines()
-        .map(|line| line.unwrap())
-        .collect::<Vec<String>>()
-        .join("\n")
+    source::read_text(std::io::stdin().lock()).unwrap()
 }
 
 pub fn read_stdin_by_line() -> std::io::Lines<std::io::StdinLock<'static>> {
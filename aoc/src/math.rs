@@ -0,0 +1,319 @@
+/// Returns the greatest common divisor of the two numbers, where gcd(0, 0) = 0.
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    a
+}
+
+/// Returns the least common multiple of the two numbers, or [None] if
+/// it overflows; lcm(0, n) = 0.
+pub fn lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// Returns the greatest common divisor of all the numbers, which is 0
+/// for an empty slice.
+pub fn gcd_all(values: &[u64]) -> u64 {
+    values.iter().fold(0, |acc, &value| gcd(acc, value))
+}
+
+/// Returns the least common multiple of all the numbers, which is 1 for
+/// an empty slice, or [None] if it overflows.
+pub fn lcm_all(values: &[u64]) -> Option<u64> {
+    values.iter().try_fold(1, |acc, &value| lcm(acc, value))
+}
+
+/// Returns (g, x, y) such that ax + by = g = gcd(a, b).
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (1, 0);
+    let (mut old_t, mut t) = (0, 1);
+
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_s, s) = (s, old_s - quotient * s);
+        (old_t, t) = (t, old_t - quotient * t);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_s, -old_t)
+    } else {
+        (old_r, old_s, old_t)
+    }
+}
+
+/// Returns the inverse of `a` modulo `modulus`, or [None] if they
+/// aren't coprime or the modulus is 0.
+pub fn mod_inverse(a: u64, modulus: u64) -> Option<u64> {
+    if modulus == 0 {
+        return None;
+    }
+
+    let (g, x, _) = extended_gcd(a as i128, modulus as i128);
+    (g == 1).then(|| x.rem_euclid(modulus as i128) as u64)
+}
+
+/// Returns (a * b) mod `modulus` without overflowing.
+fn mul_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    ((a as u128 * b as u128) % modulus as u128) as u64
+}
+
+/// Solves a system of congruences x ≡ residue (mod modulus), given as
+/// (residue, modulus) pairs whose moduli needn't be coprime.
+///
+/// Returns the solution as a congruence (residue, modulus), where the
+/// modulus is the lcm of the given moduli and the residue is the least
+/// non-negative solution; or [None] if the system is inconsistent, any
+/// modulus is 0, or the lcm overflows. An empty system is solved by
+/// every integer, so it returns (0, 1).
+pub fn crt(congruences: &[(u64, u64)]) -> Option<(u64, u64)> {
+    congruences
+        .iter()
+        .try_fold((0, 1), |(r1, m1), &(r2, m2)| {
+            if m2 == 0 {
+                return None;
+            }
+
+            let r2 = r2 % m2;
+            let g = gcd(m1, m2);
+            let modulus = lcm(m1, m2)?;
+
+            // the difference between residues must be divisible by the gcd,
+            // otherwise the two congruences contradict one another
+            let (difference, negative) = if r2 >= r1 { (r2 - r1, false) } else { (r1 - r2, true) };
+            if difference % g != 0 {
+                return None;
+            }
+
+            // solve m1 * k ≡ r2 - r1 (mod m2), reduced by the gcd
+            let reduced = m2 / g;
+            let inverse = mod_inverse((m1 / g) % reduced, reduced)
+                .expect("m1 / g and m2 / g are coprime");
+            let mut k = mul_mod((difference / g) % reduced, inverse, reduced);
+            if negative && k != 0 {
+                k = reduced - k;
+            }
+
+            // r1 < m1 and k < m2 / g, so this is less than the lcm
+            Some((r1 + m1 * k, modulus))
+        })
+}
+
+/// Returns the integer square root of the number, which is the
+/// greatest integer whose square is at most `n`.
+pub fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+
+    // Newton's method from an overestimate decreases monotonically to the root
+    let mut x = 1u128 << ((128 - n.leading_zeros()).div_ceil(2));
+    loop {
+        let next = (x + n / x) / 2;
+        if next >= x {
+            return x;
+        }
+        x = next;
+    }
+}
+
+/// The shape of the sequence produced by repeatedly applying a function
+/// to a value, which must eventually repeat if the values are finite.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// The number of steps before the sequence first enters the cycle.
+    pub start: usize,
+    /// The number of steps it takes to go around the cycle once.
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Returns the number of steps after which the sequence produces the
+    /// same value as after the given number of steps, which skips all
+    /// the whole cycles after the sequence enters the cycle.
+    pub fn reduce(&self, steps: usize) -> usize {
+        if steps < self.start {
+            steps
+        } else {
+            self.start + (steps - self.start) % self.length
+        }
+    }
+}
+
+/// Finds the cycle in the sequence produced by repeatedly applying `step`
+/// to `initial`, using Brent's algorithm; this loops forever if the
+/// sequence never repeats.
+pub fn find_cycle<T: Clone + Eq>(initial: T, mut step: impl FnMut(&T) -> T) -> Cycle {
+    // find the length of the cycle by racing a hare ahead of a tortoise which
+    // teleports to the hare whenever the distance between them doubles
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // then walk two values a cycle apart until they meet at its start
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn gcd_and_lcm_match_brute_force() {
+        let mut rng = fastrand::Rng::with_seed(11);
+
+        for _ in 0..2000 {
+            let (a, b) = (rng.u64(0..200), rng.u64(0..200));
+            let divisor = (1..=a.max(b)).rev().find(|d| a % d == 0 && b % d == 0).unwrap_or(0);
+            assert_eq!(gcd(a, b), divisor, "gcd({}, {})", a, b);
+
+            let multiple = if a == 0 || b == 0 { 0 } else { (1..=a * b).find(|m| m % a == 0 && m % b == 0).unwrap() };
+            assert_eq!(lcm(a, b), Some(multiple), "lcm({}, {})", a, b);
+        }
+
+        assert_eq!(lcm(u64::MAX, u64::MAX - 1), None);
+        assert_eq!(lcm_all(&[]), Some(1));
+        assert_eq!(gcd_all(&[]), 0);
+    }
+
+    #[test]
+    fn mod_inverse_matches_brute_force() {
+        for modulus in 0..60 {
+            for a in 0..120 {
+                let inverse = (0..modulus).find(|&x| (a * x) % modulus == 1 % modulus);
+                assert_eq!(mod_inverse(a, modulus), inverse, "{}⁻¹ mod {}", a, modulus);
+            }
+        }
+
+        // the largest prime below 2^64
+        let prime = u64::MAX - 58;
+        let inverse = mod_inverse(2, prime).unwrap();
+        assert_eq!(mul_mod(2, inverse, prime), 1);
+    }
+
+    #[test]
+    fn crt_matches_brute_force() {
+        let mut rng = fastrand::Rng::with_seed(12);
+
+        for _ in 0..2000 {
+            // small moduli, which often share factors
+            let congruences = (0..rng.usize(0..4))
+                .map(|_| (rng.u64(0..60), rng.u64(1..25)))
+                .collect::<Vec<_>>();
+            let moduli = congruences.iter().map(|&(_, modulus)| modulus).collect::<Vec<_>>();
+            let modulus = lcm_all(&moduli).unwrap();
+            let expected = (0..modulus)
+                .find(|x| congruences.iter().all(|&(r, m)| x % m == r % m))
+                .map(|x| (x, modulus));
+
+            assert_eq!(crt(&congruences), expected, "{:?}", congruences);
+        }
+    }
+
+    #[test]
+    fn crt_edge_cases() {
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(3, 0)]), None);
+        // not coprime, but consistent
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+        // not coprime, and inconsistent
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        // consecutive numbers are coprime, so their lcm overflows
+        assert_eq!(crt(&[(0, u64::MAX), (1, u64::MAX - 1)]), None);
+
+        // two primes whose product only just fits, with residues near them
+        let (p, q) = (4_294_967_291, 4_294_967_279);
+        let (residue, modulus) = crt(&[(p - 1, p), (q - 2, q)]).unwrap();
+        assert_eq!(modulus, p * q);
+        assert_eq!((residue % p, residue % q), (p - 1, q - 2));
+    }
+
+    #[test]
+    fn isqrt_brackets_the_root() {
+        let mut rng = fastrand::Rng::with_seed(13);
+        let check = |n: u128| {
+            let root = isqrt(n);
+            assert!(root * root <= n, "isqrt({}) = {}", n, root);
+            assert!((root + 1).checked_mul(root + 1).is_none_or(|square| square > n), "isqrt({}) = {}", n, root);
+        };
+
+        for n in 0..1000 {
+            check(n);
+        }
+        for _ in 0..2000 {
+            check(rng.u128(..) >> rng.u32(0..128));
+        }
+
+        let root = u64::MAX as u128;
+        for n in [u128::MAX, u128::MAX - 1, root * root, root * root - 1, (root - 1) * (root - 1)] {
+            check(n);
+        }
+        assert_eq!(isqrt(u128::MAX), root);
+        assert_eq!(isqrt(root * root - 1), root - 1);
+    }
+
+    #[test]
+    fn find_cycle_matches_brute_force() {
+        let mut rng = fastrand::Rng::with_seed(14);
+
+        for _ in 0..2000 {
+            // a random function on a few values, which must eventually cycle
+            let size = rng.usize(1..40);
+            let next = (0..size).map(|_| rng.usize(0..size)).collect::<Vec<_>>();
+            let initial = rng.usize(0..size);
+
+            let mut seen = HashMap::new();
+            let mut sequence = vec![initial];
+            let (mut value, mut steps) = (initial, 0);
+            let expected = loop {
+                if let Some(&first) = seen.get(&value) {
+                    break Cycle { start: first, length: steps - first };
+                }
+                seen.insert(value, steps);
+                value = next[value];
+                sequence.push(value);
+                steps += 1;
+            };
+
+            let cycle = find_cycle(initial, |&value| next[value]);
+            assert_eq!(cycle, expected, "{:?} from {}", next, initial);
+
+            for steps in 0..sequence.len() {
+                assert_eq!(sequence[cycle.reduce(steps)], sequence[steps]);
+            }
+        }
+    }
+}
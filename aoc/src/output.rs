@@ -0,0 +1,79 @@
+use std::{fmt::Display, str::FromStr, time::Duration};
+
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::Question;
+
+#[derive(Error, Debug)]
+pub struct FormatParseError(String);
+
+impl Display for FormatParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Expected --output [text|json]; got {}", self.0)
+    }
+}
+
+/// The format in which answers are printed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Print only the answer, as plain text.
+    #[default]
+    Text,
+    /// Print a JSON object with the answer and its timings on a single line.
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = FormatParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            other => Err(FormatParseError(String::from(other))),
+        }
+    }
+}
+
+/// The answer to a single question, along with how long it took to compute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// The day of the month on which the puzzle was released.
+    pub day: u8,
+    /// The question that was answered.
+    pub question: Question,
+    /// The displayed answer to the question.
+    pub answer: String,
+    /// The time taken to parse the source data.
+    pub parse: Duration,
+    /// The time taken to answer the question from the parsed input.
+    pub solve: Duration,
+}
+
+/// The JSON representation of an [Outcome].
+#[derive(Serialize)]
+struct Record<'a> {
+    day: u8,
+    part: usize,
+    answer: &'a str,
+    parse_ns: u128,
+    solve_ns: u128,
+}
+
+impl Outcome {
+    /// Renders this outcome in the given format.
+    pub fn render(&self, format: Format) -> String {
+        match format {
+            Format::Text => self.answer.clone(),
+            Format::Json => serde_json::to_string(&Record {
+                day: self.day,
+                part: self.question.index() + 1,
+                answer: &self.answer,
+                parse_ns: self.parse.as_nanos(),
+                solve_ns: self.solve.as_nanos(),
+            })
+            .expect("records are always serializable"),
+        }
+    }
+}
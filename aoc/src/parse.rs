@@ -0,0 +1,128 @@
+use std::str::FromStr;
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{digit1, multispace0, newline, one_of, space0, space1},
+    combinator::{all_consuming, opt, recognize},
+    error::ErrorKind,
+    multi::{many1, separated_list1},
+    sequence::{pair, preceded, terminated},
+    IResult, Parser,
+};
+
+use crate::Error;
+
+/// Parses an integer with an optional leading sign.
+///
+/// An integer which doesn't fit in `T` is reported as a failure, since
+/// no other parser will make any more sense of its digits.
+pub fn integer<T: FromStr>(source: &str) -> IResult<&str, T> {
+    let (tail, digits) = recognize(pair(opt(one_of("+-")), digit1)).parse(source)?;
+
+    match digits.parse() {
+        Ok(value) => Ok((tail, value)),
+        Err(_) => Err(nom::Err::Failure(nom::error::Error::new(source, ErrorKind::TooLarge))),
+    }
+}
+
+/// Parses a list of integers separated by spaces or tabs, which
+/// doesn't consume any trailing whitespace.
+pub fn integers<T: FromStr>(source: &str) -> IResult<&str, Vec<T>> {
+    let mut parser = separated_list1(space1, integer);
+
+    parser.parse(source)
+}
+
+/// Parses the given label, followed by any spaces or tabs, and then
+/// applies the parser to the rest of the line.
+pub fn labelled<'a, O, P>(label: &'static str, parser: P) -> impl FnMut(&'a str) -> IResult<&'a str, O>
+where
+    P: Parser<&'a str, O, nom::error::Error<&'a str>>,
+{
+    preceded(tag(label).and(space0), parser)
+}
+
+/// Parses the end of a line followed by an empty line.
+pub fn blank_line(source: &str) -> IResult<&str, ()> {
+    let mut parser = newline.and(newline);
+
+    parser.parse(source).map(|(tail, _)| (tail, ()))
+}
+
+/// Applies the parser to every line of a block of non-empty lines, stopping
+/// at a blank line or at the end of the source; the newline which ends the
+/// block is left unconsumed.
+///
+/// Each line must be consumed entirely, and because the extent of each line
+/// is known up front, a line which fails to parse is reported as a failure at
+/// the point where it went wrong rather than ending the block early.
+pub fn lines<'a, O, P>(mut parser: P) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    P: Parser<&'a str, O, nom::error::Error<&'a str>>,
+{
+    move |source: &'a str| {
+        let mut items = Vec::new();
+        let mut rest = source;
+
+        loop {
+            let (line, tail) = rest.split_at(rest.find('\n').unwrap_or(rest.len()));
+            if line.is_empty() {
+                break;
+            }
+
+            let (remaining, item) = parser.parse(line).map_err(|err| match err {
+                nom::Err::Error(err) => nom::Err::Failure(err),
+                err => err,
+            })?;
+            if !remaining.is_empty() {
+                return Err(nom::Err::Failure(nom::error::Error::new(remaining, ErrorKind::Eof)));
+            }
+
+            items.push(item);
+            rest = tail;
+
+            match tail.strip_prefix('\n') {
+                Some(next) if !next.is_empty() && !next.starts_with('\n') => rest = next,
+                _ => break,
+            }
+        }
+
+        if items.is_empty() {
+            return Err(nom::Err::Error(nom::error::Error::new(source, ErrorKind::SeparatedList)));
+        }
+
+        Ok((rest, items))
+    }
+}
+
+/// Applies the parser to every block in a series of blocks separated by
+/// blank lines.
+pub fn blocks<'a, O, P>(parser: P) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    P: Parser<&'a str, O, nom::error::Error<&'a str>>,
+{
+    separated_list1(blank_line, parser)
+}
+
+/// Parses a block of lines into rows of cells, as in a map or a picture.
+///
+/// The rows aren't required to have the same number of cells.
+pub fn grid<'a, O, P>(cell: P) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<Vec<O>>>
+where
+    P: Parser<&'a str, O, nom::error::Error<&'a str>>,
+{
+    lines(many1(cell))
+}
+
+/// Applies the parser to the whole of the source data, allowing only
+/// trailing whitespace to be left over, and converts any failure into
+/// an [Error] which owns its diagnostic.
+pub fn finish<'a, O, P>(source: &'a str, parser: P) -> Result<O, Error>
+where
+    P: Parser<&'a str, O, nom::error::Error<&'a str>>,
+{
+    all_consuming(terminated(parser, multispace0))
+        .parse(source)
+        .map(|(_, output)| output)
+        .map_err(|err| Error::from_nom(source, err))
+}
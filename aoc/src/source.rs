@@ -0,0 +1,128 @@
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use anyhow::Context;
+
+use crate::{read_stdin_to_string, Day, Question};
+
+/// Where the source data for a solution is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// Read the source data from stdin.
+    Stdin,
+    /// Read the source data from the file at the given path.
+    File(PathBuf),
+    /// Use the example input given in the puzzle description.
+    Example,
+    /// Use the given string as the source data.
+    Inline(String),
+}
+
+impl Source {
+    /// Selects a source from the command line arguments of the given day.
+    ///
+    /// An input path of `-` selects stdin, and if neither an input path
+    /// nor the example flag is given then the day's default data file
+    /// is used instead.
+    pub fn from_args(input: Option<&Path>, example: bool, day: u8) -> anyhow::Result<Self> {
+        match (input, example) {
+            (Some(_), true) => Err(anyhow::anyhow!("--input and --example are mutually exclusive")),
+            (Some(path), false) if path == Path::new("-") => Ok(Self::Stdin),
+            (Some(path), false) => Ok(Self::File(path.to_path_buf())),
+            (None, true) => Ok(Self::Example),
+            (None, false) => Ok(Self::File(data_path(day))),
+        }
+    }
+
+    /// Returns a name for the source data, for use in error messages.
+    pub fn name(&self) -> String {
+        match self {
+            Self::Stdin => String::from("<stdin>"),
+            Self::File(path) => path.display().to_string(),
+            Self::Example => String::from("<example>"),
+            Self::Inline(_) => String::from("<inline>"),
+        }
+    }
+
+    /// Reads the source data for the given question of the given day.
+    pub fn read(&self, day: &Day, question: Question) -> anyhow::Result<String> {
+        match self {
+            Self::Stdin => Ok(stdin().to_string()),
+            Self::File(path) => std::fs::File::open(path)
+                .and_then(read_text)
+                .with_context(|| format!("failed to read {}", path.display())),
+            Self::Example => Ok(normalise_line_endings(day.examples[question.index()])),
+            Self::Inline(source) => Ok(source.clone()),
+        }
+    }
+
+    /// Reads the source data for both questions of the given day, reading
+    /// from stdin or from a file at most once.
+    pub fn read_all(&self, day: &Day) -> anyhow::Result<[String; 2]> {
+        match self {
+            Self::Example => Ok(day.examples.map(String::from)),
+            _ => {
+                let source = self.read(day, Question::One)?;
+                Ok([source.clone(), source])
+            }
+        }
+    }
+}
+
+/// Replaces Windows line endings with plain newlines, so that a source
+/// checked out or saved with either gives the solvers the same text.
+fn normalise_line_endings(text: &str) -> String {
+    text.replace("\r\n", "\n")
+}
+
+/// Reads the source data from the reader to the end, the same way for
+/// files and for stdin.
+pub(crate) fn read_text(mut reader: impl Read) -> std::io::Result<String> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+    Ok(normalise_line_endings(&text))
+}
+
+/// Reads stdin to the end the first time it's called, and returns the same
+/// text every time after that, since stdin can only be read once.
+fn stdin() -> &'static str {
+    static STDIN: OnceLock<String> = OnceLock::new();
+    STDIN.get_or_init(read_stdin_to_string)
+}
+
+/// Returns the root of the workspace containing the aoc crate.
+pub fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("the aoc crate is inside the workspace")
+}
+
+/// Returns the path to the default source data for the given day,
+/// which is `data/dayN.txt` relative to the workspace root.
+pub fn data_path(day: u8) -> PathBuf {
+    workspace_root()
+        .join("data")
+        .join(format!("day{}.txt", day))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn files_and_stdin_read_the_same_text() {
+        let crlf = "467..114..\r\n...*......\r\n\r\n..35..633.\r\n";
+        let path = std::env::temp_dir().join(format!("aoc-crlf-{}.txt", std::process::id()));
+        std::fs::write(&path, crlf).unwrap();
+        let file = std::fs::File::open(&path).and_then(read_text);
+        std::fs::remove_file(&path).unwrap();
+
+        let expected = "467..114..\n...*......\n\n..35..633.\n";
+        assert_eq!(file.unwrap(), expected);
+        assert_eq!(read_text(crlf.as_bytes()).unwrap(), expected);
+        assert_eq!(read_text(expected.as_bytes()).unwrap(), expected);
+    }
+}
@@ -0,0 +1,130 @@
+use aoc::{Day, Error, Solver};
+use nom::{
+    IResult, 
+    character::complete::{alpha0, anychar}, 
+    sequence::delimited, 
+    multi::{separated_list0, many1}, 
+    character::complete::{alpha1, one_of}, 
+    Parser, 
+    combinator::{opt, map_res, map}, 
+    branch::alt, bytes::complete::tag
+};
+
+/// Parses a single digit from the source input.
+fn single_digit(source: &str) -> IResult<&str, u8> {
+    let mut parser = map_res(one_of("123456789"), |c| String::from(c).parse::<u8>());
+    parser.parse(source)
+}
+
+/// Parses a single named digit from the given source.
+fn named_digit(source: &str) -> IResult<&str, u8> {
+    let mut parser = alt((
+        tag("one"),
+        tag("two"),
+        tag("three"),
+        tag("four"),
+        tag("five"),
+        tag("six"),
+        tag("seven"),
+        tag("eight"),
+        tag("nine"),
+    ));
+
+    parser.parse(source).map(|(tail, name)| (tail, match name {
+        "one" => 1,
+        "two" => 2,
+        "three" => 3,
+        "four" => 4,
+        "five" => 5,
+        "six" => 6,
+        "seven" => 7,
+        "eight" => 8,
+        "nine" => 9,
+        _ => unreachable!(),
+    }))
+}
+
+/// Parses a line into a Vec<u8>, according to the question 1 specification.
+fn literal_digits(source: &str) -> IResult<&str, Vec<u8>> {
+    let mut parser = delimited(
+        opt(alpha0), 
+        separated_list0(
+            alpha1,
+            many1(single_digit),
+        ), 
+        opt(alpha0), 
+    );
+    parser.parse(source).map(|(tail, data)| (tail, data.into_iter().flatten().collect()))
+}
+
+/// Parses a line into a Vec<u8>, according to the question 2 specification.
+fn literal_and_named_digits(source: &str) -> IResult<&str, Vec<u8>> {
+    let mut parser = many1(
+        alt((named_digit, single_digit, map(anychar, |_| 0)))
+    );
+
+    parser.parse(source).map(|(tail, v)| (tail, v.into_iter().filter(|&x| x != 0).collect()))
+}
+
+/// The digits found in a single line of the calibration document.
+pub struct CalibrationLine {
+    /// The literal digits in the line, as in question 1.
+    literal: Vec<u8>,
+    /// The literal and named digits in the line, as in question 2.
+    named: Vec<u8>,
+}
+
+/// Combines the first and last digits of a line into a two-digit number.
+fn calibration_value(digits: &[u8]) -> Option<usize> {
+    let first = *digits.first()? as usize;
+    let last = *digits.last()? as usize;
+    Some((first * 10) + last)
+}
+
+/// Sums the calibration values of every line, using the digits
+/// selected by the given function.
+fn calibration_sum(lines: &[CalibrationLine], digits: fn(&CalibrationLine) -> &[u8]) -> Result<usize, Error> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            calibration_value(digits(line))
+                .ok_or_else(|| Error::NoAnswer(format!("line {} has no digits", i + 1)))
+        })
+        .sum()
+}
+
+/// Day 1: Trebuchet?!
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    const DAY: u8 = 1;
+    const EXAMPLES: [&'static str; 2] = [
+        include_str!("../../data/examples/day1.txt"),
+        include_str!("../../data/examples/day1-2.txt"),
+    ];
+    type Input = Vec<CalibrationLine>;
+    type Answer = usize;
+
+    fn parse(source: &str) -> Result<Self::Input, Error> {
+        source
+            .lines()
+            .map(|line| {
+                Ok(CalibrationLine {
+                    literal: literal_digits(line).map_err(|err| Error::from_nom(source, err))?.1,
+                    named: literal_and_named_digits(line).map_err(|err| Error::from_nom(source, err))?.1,
+                })
+            })
+            .collect()
+    }
+
+    fn part_one(input: &Self::Input) -> Result<usize, Error> {
+        calibration_sum(input, |line| &line.literal)
+    }
+
+    fn part_two(input: &Self::Input) -> Result<usize, Error> {
+        calibration_sum(input, |line| &line.named)
+    }
+}
+
+pub const DAY: Day = Day::of::<Puzzle>();
@@ -0,0 +1,128 @@
+use aoc::{parse::{finish, integer, labelled, lines}, Day, Error, Solver};
+use nom::{
+    IResult, 
+    multi::{fold_many_m_n, many1}, 
+    character::complete::u8, 
+    Parser, 
+    bytes::complete::tag, 
+    sequence::{separated_pair, terminated},
+    branch::alt, combinator::{opt, map},
+};
+
+/// Represents an individual set unveiled during a game.
+#[derive(Debug, Clone, Copy)]
+struct CubeSet {
+    red: usize,
+    green: usize,
+    blue: usize,
+}
+
+/// Represents a full game, with its index and cube sets.
+#[derive(Debug, Clone)]
+pub struct Game {
+    index: usize,
+    sets: Vec<CubeSet>,
+}
+
+fn parse_cube_set(source: &str) -> IResult<&str, CubeSet> {
+    let mut parser = fold_many_m_n(
+        1, 
+        3, 
+        terminated(
+            separated_pair(u8, tag(" "), alt((tag("green"), tag("blue"), tag("red")))),
+            opt(tag(", "))
+        ),
+        || {CubeSet{red: 0, green: 0, blue: 0}}, 
+        |set, (count, color)| match color {
+            "red" => CubeSet {red: set.red + (count as usize), ..set},
+            "blue" => CubeSet {blue: set.blue + (count as usize), ..set},
+            "green" => CubeSet {green: set.green + (count as usize), ..set},
+            _ => unreachable!(),
+        }
+    );
+
+    parser.parse(source)
+}
+
+fn parse_game_string(source: &str) -> IResult<&str, Game> {
+    let index = labelled("Game", terminated(integer, tag(": ")));
+    let sets = terminated(many1(parse_cube_set), opt(tag("; ")));
+    let mut parser = map(index.and(many1(sets)), |(i, s)| Game {
+        index: i, 
+        sets: s.into_iter().flatten().collect(),
+    });
+
+    parser.parse(source)
+}
+
+/// Day 2: Cube Conundrum
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    const DAY: u8 = 2;
+    const EXAMPLES: [&'static str; 2] = [include_str!("../../data/examples/day2.txt"); 2];
+    type Input = Vec<Game>;
+    type Answer = usize;
+
+    fn parse(source: &str) -> Result<Self::Input, Error> {
+        finish(source, lines(parse_game_string))
+    }
+
+    fn part_one(input: &Self::Input) -> Result<usize, Error> {
+        let mut possible_sum = 0usize;
+
+        for game in input {
+            let mut game_is_possible = true;
+
+            for set in &game.sets {
+                if set.red > 12 || set.green > 13 || set.blue > 14 {
+                    game_is_possible = false;
+                }
+            }
+
+            if game_is_possible {
+                possible_sum += game.index;
+            }
+        }
+
+        Ok(possible_sum)
+    }
+
+    fn part_two(input: &Self::Input) -> Result<usize, Error> {
+        let mut minimum_sets: Vec<CubeSet> = Vec::new();
+
+        for game in input {
+            let mut minimum_set = CubeSet { 
+                red: 0usize, 
+                green: 0usize, 
+                blue: 0usize,
+            };
+
+            for set in &game.sets {
+                if minimum_set.red < set.red {
+                    minimum_set.red = set.red;
+                }
+
+                if minimum_set.green < set.green {
+                    minimum_set.green = set.green;
+                }
+
+                if minimum_set.blue < set.blue {
+                    minimum_set.blue = set.blue;
+                }
+            }
+
+            minimum_sets.push(minimum_set);
+        }
+
+        Ok(minimum_sets
+            .into_iter()
+            .fold(0, 
+                |sum, set| 
+                sum + (set.red * set.green * set.blue)
+            )
+        )
+    }
+}
+
+pub const DAY: Day = Day::of::<Puzzle>();
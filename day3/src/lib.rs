@@ -0,0 +1,144 @@
+use aoc::{
+    grid::{Grid, Position},
+    Day, Error, Solver,
+};
+
+use std::{collections::HashMap, ops::Range};
+
+/// Represents the elements which can appear in an engine schematic.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SchematicItem {
+    Digit(u8),
+    Symbol(char),
+    Empty,
+}
+
+impl SchematicItem {
+    /// Maps a character from the source data to a schematic item.
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(SchematicItem::Empty),
+            '0'..='9' => c.to_digit(10).map(|digit| SchematicItem::Digit(digit as u8)),
+            c if c.is_whitespace() => None,
+            c => Some(SchematicItem::Symbol(c)),
+        }
+    }
+}
+
+/// A number in the schematic, which spans one or more adjacent digits in a row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartNumber {
+    value: usize,
+    row: usize,
+    columns: Range<usize>,
+}
+
+impl PartNumber {
+    /// Iterates over the items surrounding the number, including its diagonals.
+    fn surroundings<'a>(
+        &'a self,
+        grid: &'a Grid<SchematicItem>,
+    ) -> impl Iterator<Item = (Position, &'a SchematicItem)> + 'a {
+        let rows = self.row.saturating_sub(1)..self.row + 2;
+        let columns = self.columns.start.saturating_sub(1)..self.columns.end + 1;
+
+        grid.region(rows, columns)
+            .filter(|&((row, column), _)| row != self.row || !self.columns.contains(&column))
+    }
+}
+
+/// The engine schematic, along with every number found in it.
+pub struct Schematic {
+    grid: Grid<SchematicItem>,
+    numbers: Vec<PartNumber>,
+}
+
+/// Finds every run of digits in the grid parsed from the source data, and
+/// reads them as numbers.
+fn find_numbers(source: &str, grid: &Grid<SchematicItem>) -> Result<Vec<PartNumber>, Error> {
+    let mut numbers = Vec::new();
+
+    for ((row, items), line) in grid.rows().enumerate().zip(source.lines()) {
+        let mut column = 0;
+
+        while column < items.len() {
+            let start = column;
+            let mut value = 0usize;
+
+            while let Some(&SchematicItem::Digit(digit)) = items.get(column) {
+                value = value
+                    .checked_mul(10)
+                    .and_then(|value| value.checked_add(digit as usize))
+                    .ok_or_else(|| {
+                        let (offset, _) = line.char_indices().nth(start).expect("the grid was parsed from this line");
+                        Error::parse(source, &line[offset..], "number out of range")
+                    })?;
+                column += 1;
+            }
+
+            if column > start {
+                numbers.push(PartNumber { value, row, columns: start..column });
+            } else {
+                column += 1;
+            }
+        }
+    }
+
+    Ok(numbers)
+}
+
+/// Reads all the lines of the source data and constructs a representation of the input.
+fn get_full_input(source: &str) -> Result<Schematic, Error> {
+    let grid = Grid::parse(source, SchematicItem::from_char)?;
+    let numbers = find_numbers(source, &grid)?;
+
+    Ok(Schematic { grid, numbers })
+}
+
+/// Day 3: Gear Ratios
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    const DAY: u8 = 3;
+    const EXAMPLES: [&'static str; 2] = [include_str!("../../data/examples/day3.txt"); 2];
+    type Input = Schematic;
+    type Answer = usize;
+
+    fn parse(source: &str) -> Result<Self::Input, Error> {
+        get_full_input(source)
+    }
+
+    fn part_one(schematic: &Self::Input) -> Result<usize, Error> {
+        Ok(schematic
+            .numbers
+            .iter()
+            .filter(|number| {
+                number
+                    .surroundings(&schematic.grid)
+                    .any(|(_, item)| matches!(item, SchematicItem::Symbol(_)))
+            })
+            .map(|number| number.value)
+            .sum())
+    }
+
+    fn part_two(schematic: &Self::Input) -> Result<usize, Error> {
+        let mut gear_candidates: HashMap<Position, Vec<usize>> = HashMap::new();
+
+        for number in &schematic.numbers {
+            for (position, item) in number.surroundings(&schematic.grid) {
+                if let SchematicItem::Symbol('*') = item {
+                    gear_candidates.entry(position).or_default().push(number.value);
+                }
+            }
+        }
+
+        Ok(gear_candidates.into_values().filter_map(|nums| match nums.len() {
+                2 => Some(nums[0] * nums[1]),
+                _ => None,
+            })
+            .sum::<usize>()
+        )
+    }
+}
+
+pub const DAY: Day = Day::of::<Puzzle>();
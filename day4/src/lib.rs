@@ -0,0 +1,150 @@
+use std::str::FromStr;
+
+use aoc::{parse::{finish, integer, integers, labelled, lines}, Day, Error, Solver};
+use nom::{
+    IResult, 
+    sequence::terminated, 
+    bytes::complete::tag, 
+    character::complete::space1, 
+    Parser, 
+    combinator::{map, opt}, 
+};
+
+/// Represents an individual scratchcard
+pub struct Card {
+    /// The ID number of the card, which denotes its position in the sequence.
+    id: usize,
+    /// The marked winning numbers, to the left of the bar.
+    ///
+    /// Technically this would be more efficient to store in
+    /// a [usize; 10] since there are always 10 values in the
+    /// given input, but I don't want to bother with lifetimes
+    /// and manual indices.
+    winning: Vec<usize>,
+    /// The actual numbers given to the holder of the scratchcard.
+    ///
+    /// This could also be stored in a constant-length array, as a
+    /// [usize; 25], but as with the winning numbers, I don't really
+    /// want to deal with that manually.
+    actual: Vec<usize>,
+}
+
+impl Card {
+    /// Returns the number of points that this card is worth.
+    fn matches(&self) -> usize {
+        let mut matches = 0;
+        
+        for winning in &self.winning {
+            for actual in &self.actual {
+                if winning == actual {
+                    matches += 1;
+                }
+            }
+        }
+
+        matches
+    }
+
+    fn points(&self) -> usize {
+        let match_count: u32 = self
+            .matches()
+            .try_into()
+            .unwrap();
+
+        if match_count == 0 {
+            0
+        } else {
+            2usize.pow(match_count - 1)
+        }
+    }
+}
+
+/// Parses a single line of the source data into a card.
+fn parse_card(source: &str) -> IResult<&str, Card> {
+    let mut parser = map(
+        card.and(number_list).and(number_list),
+        |((id, winning), actual)| Card { id, winning, actual },
+    );
+
+    parser.parse(source)
+}
+
+// The entrypoint to the parser is implemented here.
+impl FromStr for Card {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        finish(s, parse_card)
+    }
+}
+
+/// Parses the leading section of an input line, including trailing whitespace.
+///
+/// The source data has a bunch of variable-length whitespace, so I'm using the
+/// [space1] function to handle it. The reason for this seems to be that
+/// the extra whitespace makes each input line the exact same length (116).
+fn card(source: &str) -> IResult<&str, usize> {
+    let mut parser = labelled(
+        "Card", 
+        terminated(
+            integer, 
+            tag(":").and(space1)
+        )
+    );
+
+    parser.parse(source)
+}
+
+/// Parses a whitespace-delimited list of integers, optionally followed by a sequence
+/// of whitespace, a single "|" character, and a sequence of whitespace.
+///
+/// This function can parse both the winning and actual numbers from the source string,
+/// but will only consume one at a time.
+fn number_list(source: &str) -> IResult<&str, Vec<usize>> {
+    let mut parser = terminated(
+        integers, 
+        opt(space1.and(tag("|")).and(space1)));
+
+    parser.parse(source)
+}
+
+/// Day 4: Scratchcards
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    const DAY: u8 = 4;
+    const EXAMPLES: [&'static str; 2] = [include_str!("../../data/examples/day4.txt"); 2];
+    type Input = Vec<Card>;
+    type Answer = usize;
+
+    fn parse(source: &str) -> Result<Self::Input, Error> {
+        finish(source, lines(parse_card))
+    }
+
+    /// The answer to question 1 is defined as the sum of the number of
+    /// points in the input, and the points are defined as 2^(k-1) where k
+    /// is the number of actual numbers that are also winning numbers. Note
+    /// the exceptional case where k = 0, in which the result should be 0.
+    fn part_one(cards: &Self::Input) -> Result<usize, Error> {
+        Ok(cards.iter().map(Card::points).sum())
+    }
+
+    /// The answer to question 2 is defined as the total number of scratchcards,
+    /// and where each card generates additional new cards based on the number
+    /// of matches. New cards are generated sequentially, such that if card 6 has
+    /// three matches, then it also generates cards 7, 8, and 9.
+    fn part_two(cards: &Self::Input) -> Result<usize, Error> {
+        let mut multiplicities: Vec<usize> = Vec::with_capacity(cards.len());
+        for _ in 0..cards.len() { multiplicities.push(1) };
+
+        for card in cards {
+            for i in 1..=card.matches() {
+                multiplicities[card.id + i - 1] += multiplicities[card.id - 1];
+            }
+        }
+            
+        Ok(multiplicities.into_iter().sum())
+    }
+}
+
+pub const DAY: Day = Day::of::<Puzzle>();
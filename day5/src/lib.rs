@@ -0,0 +1,348 @@
+use aoc::{interval::{IntervalSet, OffsetMap}, parse::{blank_line, blocks, finish, integer, integers, labelled, lines}, Day, Error, Question, Solver};
+use nom::{
+    bytes::complete::{is_not, tag},
+    character::complete::newline,
+    combinator::consumed,
+    sequence::{preceded, separated_pair, Tuple},
+    IResult, Parser,
+};
+use std::fmt::Display;
+
+/// A map between two categories, which moves each range listed
+/// in the source data by a fixed offset and leaves every other
+/// value where it is.
+#[derive(Debug, Clone, Default)]
+pub struct IdRangeMap(OffsetMap<u64>);
+
+impl IdRangeMap {
+    /// Returns the image of the argument under self.
+    pub fn map_value(&self, value: u64) -> u64 {
+        self.0.apply(value)
+    }
+
+    /// Returns the image of the given ranges under self.
+    pub fn map_ranges(&self, ranges: &IntervalSet<u64>) -> IntervalSet<u64> {
+        self.0.image(ranges)
+    }
+
+    /// Returns every value whose image under self is in the given ranges.
+    pub fn preimage(&self, ranges: &IntervalSet<u64>) -> IntervalSet<u64> {
+        self.0.preimage(ranges)
+    }
+
+    /// Returns the map which applies self and then the other map.
+    pub fn compose(&self, other: &IdRangeMap) -> IdRangeMap {
+        IdRangeMap(self.0.then(&other.0))
+    }
+}
+
+/// Prints the map as a table in the same format as the source data,
+/// with one "target start, source start, length" line per range.
+impl Display for IdRangeMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, (domain, image)) in self.0.pieces().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{} {} {}", image.start, domain.start, domain.end - domain.start)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Represents the complete source data, with the maps stored
+/// in-order such that applying them sequentially will produce
+/// a seed-location mapping.
+#[derive(Debug, Clone)]
+pub struct Almanac {
+    /// The category maps given by each individual map.
+    maps: Vec<IdRangeMap>,
+    /// The composition of every map, in order.
+    seed_to_location: IdRangeMap,
+}
+
+impl Almanac {
+    /// Constructs an almanac from its maps, given in order.
+    fn new(maps: Vec<IdRangeMap>) -> Self {
+        let seed_to_location = maps
+            .iter()
+            .fold(IdRangeMap::default(), |composed, map| composed.compose(map));
+
+        Almanac { maps, seed_to_location }
+    }
+
+    /// The category maps, in the order they're applied.
+    pub fn maps(&self) -> &[IdRangeMap] {
+        &self.maps
+    }
+
+    /// The single map which takes each seed directly to its location,
+    /// which is equivalent to applying every map in turn.
+    pub fn seed_to_location(&self) -> &IdRangeMap {
+        &self.seed_to_location
+    }
+
+    /// Returns every seed whose location is in the given ranges, by
+    /// following the locations back through each map in reverse.
+    pub fn seeds_for(&self, locations: &IntervalSet<u64>) -> IntervalSet<u64> {
+        self.maps
+            .iter()
+            .rev()
+            .fold(locations.clone(), |ranges, map| map.preimage(&ranges))
+    }
+
+    /// Returns the lowest location of any of the given seeds, found by
+    /// searching for the shortest range of locations starting at 0 whose
+    /// seeds include one of the given seeds; this never maps a seed
+    /// forwards, so it's an independent check on [IdRangeMap::map_ranges].
+    pub fn lowest_location(&self, seeds: &IntervalSet<u64>) -> Option<u64> {
+        let reaches = |end: u64| {
+            !self
+                .seeds_for(&IntervalSet::from(0..end))
+                .intersection(seeds)
+                .is_empty()
+        };
+
+        if !reaches(u64::MAX) {
+            return None;
+        }
+
+        // reaches(low) is always false, and reaches(high) is always true
+        let (mut low, mut high) = (0, u64::MAX);
+        while high - low > 1 {
+            let mid = low + (high - low) / 2;
+            if reaches(mid) {
+                high = mid;
+            } else {
+                low = mid;
+            }
+        }
+
+        Some(high - 1)
+    }
+}
+
+/// Parses the first line of the input into a list of seeds.
+fn seeds(source: &str) -> IResult<&str, Vec<u64>> {
+    let mut parser = labelled("seeds:", integers);
+
+    parser.parse(source)
+}
+
+/// A line in a map, as the start of the target range, the start of the
+/// source range and the length of both ranges.
+type MapLine = (u64, u64, u64);
+
+/// Parses an individual line in a map.
+fn map_line(source: &str) -> IResult<&str, MapLine> {
+    let mut parser = (
+        integer::<u64>,
+        preceded(tag(" "), integer::<u64>),
+        preceded(tag(" "), integer::<u64>),
+    );
+
+    parser.parse(source)
+}
+
+/// Parses a complete map, keeping the text of each line so that
+/// the ranges can be checked against it afterwards.
+fn map(source: &str) -> IResult<&str, Vec<(&str, MapLine)>> {
+    let mut parser = preceded(is_not("\n").and(newline), lines(consumed(map_line)));
+
+    parser.parse(source)
+}
+
+/// Parses the given input and returns a list of seed values and an almanac composed
+/// of the mappings defined by the input.
+fn read_input(source: &str) -> Result<(Vec<u64>, Almanac), Error> {
+    let (seeds, maps) = finish(source, separated_pair(seeds, blank_line, blocks(map)))?;
+
+    let mut id_range_maps = Vec::with_capacity(maps.len());
+
+    for map in maps {
+        let mut offset_map = OffsetMap::new();
+        for (line, (target_start, source_start, len)) in map {
+            let source_end = source_start
+                .checked_add(len)
+                .ok_or_else(|| Error::parse(source, line, "the source range ends beyond the largest u64"))?;
+
+            if !offset_map.insert(source_start..source_end, target_start) {
+                return Err(Error::parse(source, line, "the target range ends beyond the largest u64"));
+            }
+        }
+        id_range_maps.push(IdRangeMap(offset_map));
+    }
+
+    Ok((seeds, Almanac::new(id_range_maps)))
+}
+
+/// Reads the seeds as pairs of the start and length of each range of seeds.
+fn seed_ranges(seeds: &[u64]) -> Result<IntervalSet<u64>, Error> {
+    if !seeds.len().is_multiple_of(2) {
+        return Err(Error::NoAnswer(format!(
+            "the seed {} has no length to pair it with",
+            seeds[seeds.len() - 1]
+        )));
+    }
+
+    seeds
+        .chunks_exact(2)
+        .map(|chunk| match chunk[0].checked_add(chunk[1]) {
+            Some(end) => Ok(chunk[0]..end),
+            None => Err(Error::NoAnswer(format!(
+                "the seed range starting at {} with length {} ends beyond the largest u64",
+                chunk[0], chunk[1]
+            ))),
+        })
+        .collect()
+}
+
+/// Finds the lowest location of any seed in the ranges read from the
+/// seeds, with the given search over the ranges.
+fn lowest_location_of_ranges(
+    seeds: &[u64],
+    lowest_location: impl FnOnce(&IntervalSet<u64>) -> Option<u64>,
+) -> Result<u64, Error> {
+    lowest_location(&seed_ranges(seeds)?).ok_or_else(|| Error::NoAnswer(String::from("there are no seed ranges")))
+}
+
+/// Day 5: If You Give A Seed A Fertilizer
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    const DAY: u8 = 5;
+    const EXAMPLES: [&'static str; 2] = [include_str!("../../data/examples/day5.txt"); 2];
+    type Input = (Vec<u64>, Almanac);
+    type Answer = u64;
+    const STRATEGIES: &'static [&'static str] = &["composed", "preimage"];
+
+    fn parse(source: &str) -> Result<Self::Input, Error> {
+        read_input(source)
+    }
+
+    /// The answer to this question is the lowest location number that
+    /// corresponds to any of the initial seeds; equivalently this is just
+    /// the minimum of the image of the seeds under the composition of
+    /// all the given maps.
+    fn part_one((seeds, almanac): &Self::Input) -> Result<u64, Error> {
+        seeds
+            .iter()
+            .map(|&seed| almanac.seed_to_location().map_value(seed))
+            .min()
+            .ok_or_else(|| Error::NoAnswer(String::from("there are no seeds")))
+    }
+
+    /// The seeds are given as pairs of the start and length of each range
+    /// of seeds, and the answer is the minimum of the image of all those
+    /// ranges under the composition of all the given maps; which is
+    /// computed range by range, without visiting every seed.
+    fn part_two((seeds, almanac): &Self::Input) -> Result<u64, Error> {
+        lowest_location_of_ranges(seeds, |ranges| almanac.seed_to_location().map_ranges(ranges).min())
+    }
+
+    /// The "preimage" strategy answers question 2 by searching back from
+    /// the locations instead, with [Almanac::lowest_location]; question 1
+    /// is answered the same way by either strategy.
+    fn answer(question: Question, input: &Self::Input, strategy: Option<&str>) -> Result<u64, Error> {
+        let (seeds, almanac) = input;
+
+        match (question, strategy) {
+            (Question::One, _) => Self::part_one(input),
+            (Question::Two, Some("preimage")) => {
+                lowest_location_of_ranges(seeds, |ranges| almanac.lowest_location(ranges))
+            }
+            (Question::Two, _) => Self::part_two(input),
+        }
+    }
+}
+
+pub const DAY: Day = Day::of::<Puzzle>();
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Seed ranges which overlap one another, and maps whose ranges start
+    /// and end partway through the seed ranges and each other's images.
+    const OVERLAPPING: &str = "\
+seeds: 5 10 12 8 40 3 0 1 9 2
+
+a-to-b map:
+20 5 3
+0 8 5
+100 14 10
+13 40 1
+
+b-to-c map:
+3 0 2
+50 2 30
+1 100 5
+";
+
+    /// Finds the lowest location of any seed in the ranges by applying
+    /// each map in turn to every seed.
+    fn lowest_location_by_brute_force((seeds, almanac): &(Vec<u64>, Almanac)) -> Option<u64> {
+        seed_ranges(seeds)
+            .unwrap()
+            .ranges()
+            .iter()
+            .flat_map(|range| range.clone())
+            .map(|seed| almanac.maps().iter().fold(seed, |value, map| map.map_value(value)))
+            .min()
+    }
+
+    #[test]
+    fn example() {
+        let input = Puzzle::parse(Puzzle::EXAMPLES[0]).unwrap();
+        assert_eq!(Puzzle::part_one(&input).unwrap(), 35);
+        assert_eq!(Puzzle::part_two(&input).unwrap(), 46);
+    }
+
+    #[test]
+    fn overlapping_ranges() {
+        let input = Puzzle::parse(OVERLAPPING).unwrap();
+        let expected = lowest_location_by_brute_force(&input).unwrap();
+        assert_eq!(Puzzle::part_two(&input).unwrap(), expected);
+    }
+
+    /// Writes the source data for an almanac of a few maps, each moving a
+    /// few disjoint ranges of small values, and a few small seed ranges.
+    fn random_source(rng: &mut fastrand::Rng) -> String {
+        let seeds: Vec<_> = (0..2 * rng.usize(1..4)).map(|_| rng.u64(0..40).to_string()).collect();
+        let mut source = format!("seeds: {}\n", seeds.join(" "));
+
+        for map in 0..rng.usize(1..5) {
+            source.push_str(&format!("\n{}-to-{} map:\n", map, map + 1));
+            let mut start = 0;
+            for _ in 0..rng.usize(1..5) {
+                start += rng.u64(0..8);
+                let length = rng.u64(1..12);
+                source.push_str(&format!("{} {} {}\n", rng.u64(0..60), start, length));
+                start += length;
+            }
+        }
+
+        source
+    }
+
+    #[test]
+    fn strategies_agree() {
+        let mut rng = fastrand::Rng::with_seed(5);
+        for _ in 0..500 {
+            let source = random_source(&mut rng);
+            let input = Puzzle::parse(&source).unwrap();
+            let expected = lowest_location_by_brute_force(&input);
+            for &strategy in Puzzle::STRATEGIES {
+                let lowest = Puzzle::answer(Question::Two, &input, Some(strategy)).ok();
+                assert_eq!(lowest, expected, "{} strategy on:\n{}", strategy, source);
+            }
+        }
+    }
+
+    #[test]
+    fn unpaired_seed() {
+        let input = Puzzle::parse("seeds: 79 14 55\n\nseed-to-soil map:\n50 98 2\n").unwrap();
+        assert!(matches!(Puzzle::part_two(&input), Err(Error::NoAnswer(_))));
+    }
+}
@@ -0,0 +1,270 @@
+use aoc::{math::isqrt, parse::{finish, integers, labelled}, Day, Error, Question, Solver};
+use nom::{
+    character::complete::{digit1, newline, space1},
+    error::ErrorKind,
+    multi::separated_list1,
+    sequence::Tuple,
+    IResult, Parser,
+};
+
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+/// Represents a single race (column) from the source data.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Race {
+    /// The duration of the race in milliseconds.
+    duration: u128,
+    /// The record distance in the race in millimeters.
+    record_distance: u128,
+}
+
+/// Parses the entire input into a list of races.
+fn parse_inputs(source: &str) -> IResult<&str, Vec<Race>> {
+    let times = labelled("Time:", integers);
+    let distances = labelled("Distance:", integers);
+
+    (times, newline, distances)
+        .parse(source)
+        .map(|(tail, (times, _, distances))| {
+            (
+                tail,
+                times
+                    .into_iter()
+                    .zip(distances)
+                    .map(|(duration, record_distance)| Race {
+                        duration,
+                        record_distance,
+                    })
+                    .collect(),
+            )
+        })
+}
+
+/// Parses a number whose digits have been split into groups by bad kerning,
+/// failing if the whole number doesn't fit into a [u128].
+fn kerned_integer(source: &str) -> IResult<&str, u128> {
+    let (tail, groups) = separated_list1(space1, digit1).parse(source)?;
+
+    match groups.concat().parse() {
+        Ok(value) => Ok((tail, value)),
+        Err(_) => Err(nom::Err::Failure(nom::error::Error::new(source, ErrorKind::TooLarge))),
+    }
+}
+
+/// Parses the entire input into a single race.
+fn parse_single_race(source: &str) -> IResult<&str, Race> {
+    let time = labelled("Time:", kerned_integer);
+    let distance = labelled("Distance:", kerned_integer);
+
+    (time, newline, distance)
+        .parse(source)
+        .map(|(tail, (duration, _, record_distance))| {
+            (
+                tail,
+                Race {
+                    duration,
+                    record_distance,
+                },
+            )
+        })
+}
+
+/// Parses the source data into a list of races.
+fn get_races(source: &str) -> Result<Vec<Race>, Error> {
+    finish(source, parse_inputs)
+}
+
+/// Parses the source data into a single race.
+fn get_race(source: &str) -> Result<Race, Error> {
+    finish(source, parse_single_race)
+}
+
+impl Race {
+    /// Whether holding the button for the given time beats the record.
+    fn wins_with(&self, hold: u128) -> bool {
+        let (duration, record) = (self.duration, self.record_distance);
+
+        // a distance too large to represent certainly beats the record
+        hold.checked_mul(duration - hold).is_none_or(|distance| distance > record)
+    }
+
+    /// Counts the hold times which beat the record exactly, by solving
+    /// hold * (duration - hold) > record as a quadratic in the hold time.
+    ///
+    /// The winning hold times are symmetric about duration / 2, so it's
+    /// enough to find the shortest one; which the quadratic formula gives
+    /// to within rounding, and is then corrected by checking its neighbours.
+    fn ways_to_win(&self) -> u128 {
+        let (duration, record) = (self.duration, self.record_distance);
+        if !self.wins_with(duration / 2) {
+            return 0;
+        }
+
+        let discriminant = duration
+            .checked_mul(duration)
+            .zip(record.checked_mul(4))
+            .map(|(square, record)| square.saturating_sub(record));
+
+        let mut shortest = match discriminant {
+            Some(discriminant) => (duration - isqrt(discriminant)) / 2,
+            // when the discriminant isn't representable, search for the
+            // boundary instead, which is still only logarithmic
+            None => {
+                let (mut low, mut high) = (0, duration / 2);
+                while low < high {
+                    let mid = low + (high - low) / 2;
+                    if self.wins_with(mid) {
+                        high = mid;
+                    } else {
+                        low = mid + 1;
+                    }
+                }
+                low
+            }
+        };
+
+        while shortest > 0 && self.wins_with(shortest - 1) {
+            shortest -= 1;
+        }
+        while !self.wins_with(shortest) {
+            shortest += 1;
+        }
+
+        duration - 2 * shortest + 1
+    }
+
+    /// Counts the hold times which beat the record by trying every one.
+    fn ways_to_win_brute(&self) -> u128 {
+        (0..=self.duration)
+            .into_par_iter()
+            .filter(|&hold| self.wins_with(hold))
+            .count() as u128
+    }
+}
+
+/// Multiplies the number of ways to win each race, with the given
+/// method of counting them.
+fn margin_of_error(races: &[Race], ways_to_win: fn(&Race) -> u128) -> Result<u128, Error> {
+    races
+        .iter()
+        .map(ways_to_win)
+        .try_fold(1u128, |product, ways| product.checked_mul(ways))
+        .ok_or_else(|| Error::NoAnswer(String::from("the number of ways to win overflows")))
+}
+
+/// Day 6: Wait For It
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    const DAY: u8 = 6;
+    const EXAMPLES: [&'static str; 2] = [include_str!("../../data/examples/day6.txt"); 2];
+    /// The source data read both as a list of races (question 1)
+    /// and as a single race (question 2); reading the single race
+    /// fails only question 2, since its joined numbers can be too
+    /// large even when the numbers of each race are not.
+    type Input = (Vec<Race>, Result<Race, Error>);
+    type Answer = u128;
+    /// Either solve each race in closed form, or try every hold time.
+    const STRATEGIES: &'static [&'static str] = &["closed-form", "brute"];
+
+    fn parse(source: &str) -> Result<Self::Input, Error> {
+        Ok((get_races(source)?, get_race(source)))
+    }
+
+    fn part_one((races, _): &Self::Input) -> Result<u128, Error> {
+        margin_of_error(races, Race::ways_to_win)
+    }
+
+    fn part_two((_, race): &Self::Input) -> Result<u128, Error> {
+        margin_of_error(std::slice::from_ref(race.as_ref().map_err(Clone::clone)?), Race::ways_to_win)
+    }
+
+    fn answer(question: Question, (races, race): &Self::Input, strategy: Option<&str>) -> Result<u128, Error> {
+        let ways_to_win = match strategy {
+            Some("brute") => Race::ways_to_win_brute,
+            _ => Race::ways_to_win,
+        };
+
+        match question {
+            Question::One => margin_of_error(races, ways_to_win),
+            Question::Two => margin_of_error(std::slice::from_ref(race.as_ref().map_err(Clone::clone)?), ways_to_win),
+        }
+    }
+}
+
+pub const DAY: Day = Day::of::<Puzzle>();
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn race(duration: u128, record_distance: u128) -> Race {
+        Race {
+            duration,
+            record_distance,
+        }
+    }
+
+    #[test]
+    fn example() {
+        let input = Puzzle::parse(Puzzle::EXAMPLES[0]).unwrap();
+        assert_eq!(Puzzle::part_one(&input).unwrap(), 288);
+        assert_eq!(Puzzle::part_two(&input).unwrap(), 71503);
+    }
+
+    #[test]
+    fn kerned_race_too_large() {
+        // joining the durations gives 40 digits, too many for a u128
+        let source = "Time:     10000000 10000000 10000000 10000000 10000000\n\
+                      Distance: 24999999999999 24999999999999 24999999999999 24999999999999 24999999999999\n";
+        let input = Puzzle::parse(source).unwrap();
+        assert_eq!(Puzzle::part_one(&input).unwrap(), 1);
+        assert!(matches!(Puzzle::part_two(&input), Err(Error::Parse(_))));
+    }
+
+    #[test]
+    fn closed_form_matches_brute() {
+        let mut rng = fastrand::Rng::with_seed(6);
+        for _ in 0..2000 {
+            let duration = rng.u128(0..200);
+            let record = rng.u128(0..=duration * duration / 4 + 2);
+            let race = race(duration, record);
+            assert_eq!(race.ways_to_win(), race.ways_to_win_brute(), "{:?}", race);
+        }
+    }
+
+    #[test]
+    fn record_set_by_a_hold_time() {
+        // the hold time which set the record only ties it, so the winning
+        // hold times are strictly between it and its mirror image
+        for duration in [0, 1, 2, 7, 30, 1_000_000_000_000_000_000] {
+            for hold in [0, 1, duration / 3, duration / 2].into_iter().filter(|&hold| hold <= duration / 2) {
+                let race = race(duration, hold * (duration - hold));
+                let expected = (duration - 2 * hold).saturating_sub(1);
+                assert_eq!(race.ways_to_win(), expected, "{:?}", race);
+                if duration < 100 {
+                    assert_eq!(race.ways_to_win_brute(), expected, "{:?}", race);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn record_of_zero() {
+        // every hold time but the shortest and the longest moves the boat
+        for duration in [0, 1, 2, 3, 50, u128::MAX] {
+            assert_eq!(race(duration, 0).ways_to_win(), duration.saturating_sub(1));
+        }
+    }
+
+    #[test]
+    fn durations_near_the_largest_u128() {
+        let max = u128::MAX;
+        assert_eq!(race(max, max).ways_to_win(), max - 3);
+        assert_eq!(race(max, max - 1).ways_to_win(), max - 3);
+        assert_eq!(race(max - 1, max).ways_to_win(), max - 4);
+        assert_eq!(race(max, 1).ways_to_win(), max - 1);
+        assert_eq!(race(max, max - 2).ways_to_win(), max - 1);
+        assert_eq!(race(max - 1, 0).ways_to_win(), max - 2);
+    }
+}
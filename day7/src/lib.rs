@@ -0,0 +1,462 @@
+use std::{collections::HashMap, fmt::Display};
+
+use aoc::{parse::lines, Day, Error, Question, Solver};
+use nom::{
+    character::complete::{anychar, multispace0, space1, u32},
+    combinator::{all_consuming, map, map_opt, map_res},
+    error::ErrorKind,
+    multi::count,
+    sequence::terminated,
+    IResult, Parser,
+};
+
+/// A card, identified by its strength under some [Ruleset], where 0 is the
+/// weakest card.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Card(u8);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HandType {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+impl Display for HandType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            HandType::HighCard => "high card",
+            HandType::OnePair => "one pair",
+            HandType::TwoPair => "two pair",
+            HandType::ThreeOfAKind => "three of a kind",
+            HandType::FullHouse => "full house",
+            HandType::FourOfAKind => "four of a kind",
+            HandType::FiveOfAKind => "five of a kind",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+/// Classifies a hand by the number of each kind of card it holds, from the
+/// most common kind to the least, as in the standard game of Camel Cards.
+pub fn classify_by_counts(counts: &[usize]) -> HandType {
+    match counts {
+        [n, ..] if *n >= 5 => HandType::FiveOfAKind,
+        [4, ..] => HandType::FourOfAKind,
+        [3, n, ..] if *n >= 2 => HandType::FullHouse,
+        [3, ..] => HandType::ThreeOfAKind,
+        [2, 2, ..] => HandType::TwoPair,
+        [2, ..] => HandType::OnePair,
+        _ => HandType::HighCard,
+    }
+}
+
+/// How two hands of the same type are compared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TieBreaker {
+    /// Compare the cards in the order they were dealt, where the first
+    /// stronger card wins.
+    Dealt,
+    /// Compare the cards from strongest to weakest, as in poker.
+    Strongest,
+}
+
+/// The rules which decide what the cards are and how hands rank against
+/// one another, so that each variant of the game is just another value.
+#[derive(Debug, Clone, Copy)]
+pub struct Ruleset {
+    /// The label of every card, from the weakest card to the strongest.
+    pub labels: &'static str,
+    /// The labels of the cards which act as whichever card makes the
+    /// strongest hand type.
+    pub wildcards: &'static str,
+    /// The number of cards in each hand.
+    pub hand_size: usize,
+    /// Classifies a hand from the number of each kind of card it holds,
+    /// sorted from the most common kind to the least.
+    pub classify: fn(&[usize]) -> HandType,
+    /// How hands of the same type are compared.
+    pub tie_breaker: TieBreaker,
+}
+
+impl Ruleset {
+    /// The rules of question 1.
+    pub const STANDARD: Ruleset = Ruleset {
+        labels: "23456789TJQKA",
+        wildcards: "",
+        hand_size: 5,
+        classify: classify_by_counts,
+        tie_breaker: TieBreaker::Dealt,
+    };
+
+    /// The rules of question 2, where J is a joker, which is the weakest
+    /// card on its own but stands in for any other card.
+    pub const JOKERS: Ruleset = Ruleset {
+        labels: "J23456789TQKA",
+        wildcards: "J",
+        ..Ruleset::STANDARD
+    };
+
+    /// Maps a card label from the input to its card.
+    pub fn card(&self, label: char) -> Option<Card> {
+        self.labels.chars().position(|c| c == label).map(|index| Card(index as u8))
+    }
+
+    /// The label of the card.
+    pub fn label(&self, card: Card) -> char {
+        self.labels.chars().nth(card.0 as usize).expect("cards come from this ruleset")
+    }
+
+    /// Whether the card is a wildcard.
+    pub fn is_wild(&self, card: Card) -> bool {
+        self.wildcards.contains(self.label(card))
+    }
+
+    /// Deals a hand of the given cards, whose wildcards all stand in for
+    /// whichever card gives the strongest hand type; where several cards
+    /// give the same type, the most common of them in the hand is chosen,
+    /// and then the strongest.
+    pub fn hand(&self, cards: Vec<Card>) -> Hand {
+        let mut card_counts = HashMap::new();
+        let mut wildcards = 0;
+
+        for &card in &cards {
+            if self.is_wild(card) {
+                wildcards += 1;
+            } else {
+                *card_counts.entry(card).or_insert(0) += 1;
+            }
+        }
+
+        // classifies the hand with every wildcard counted as the given card
+        let classify_as = |wildcard_as: Option<Card>| {
+            let mut card_counts = card_counts.clone();
+            match wildcard_as {
+                Some(card) => *card_counts.entry(card).or_insert(0) += wildcards,
+                None if wildcards > 0 => return (self.classify)(&[wildcards]),
+                None => (),
+            }
+
+            let mut counts: Vec<usize> = card_counts.into_values().collect();
+            counts.sort_unstable_by(|a, b| b.cmp(a));
+            (self.classify)(&counts)
+        };
+
+        // a hand of only wildcards still tries every card which isn't one
+        let wildcard_as = (wildcards > 0)
+            .then(|| {
+                (0..self.labels.len() as u8)
+                    .map(Card)
+                    .filter(|&card| !self.is_wild(card))
+                    .max_by_key(|card| (classify_as(Some(*card)), card_counts.get(card).copied().unwrap_or(0), *card))
+            })
+            .flatten();
+
+        let mut tie_break = cards.clone();
+        if self.tie_breaker == TieBreaker::Strongest {
+            tie_break.sort_unstable_by(|a, b| b.cmp(a));
+        }
+
+        Hand {
+            cards,
+            hand_type: classify_as(wildcard_as),
+            wildcard_as,
+            tie_break,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Hand {
+    cards: Vec<Card>,
+    hand_type: HandType,
+    /// The card which every wildcard in the hand stands in for, if it has any.
+    wildcard_as: Option<Card>,
+    /// The cards in the order which the ruleset compares them when
+    /// breaking a tie between hands of the same type.
+    tie_break: Vec<Card>,
+}
+
+impl Hand {
+    /// The cards in the order they were dealt.
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    /// The type of the hand, with any wildcards resolved.
+    pub fn hand_type(&self) -> HandType {
+        self.hand_type
+    }
+
+    /// The card which every wildcard in the hand stands in for, if it has any.
+    pub fn wildcard_as(&self) -> Option<Card> {
+        self.wildcard_as
+    }
+
+    /// The key which hands are ordered by: first by type, and then card
+    /// by card in the order given by the ruleset's tie-breaker.
+    fn strength(&self) -> (HandType, &[Card]) {
+        (self.hand_type, &self.tie_break)
+    }
+}
+
+/// Hands are equal when neither beats the other, which agrees with [Ord].
+impl PartialEq for Hand {
+    fn eq(&self, other: &Self) -> bool {
+        self.strength() == other.strength()
+    }
+}
+
+impl Eq for Hand {}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Orders hands by type, breaking ties between hands of the same type with
+/// the cards, so that every pair of hands is comparable.
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.strength().cmp(&other.strength())
+    }
+}
+
+/// Parses a single line from the input and returns it, using the ruleset
+/// to map each character in the "hand" section of the data to a card,
+/// failing on characters which are not a valid card label.
+fn parse_hand_and_bid<'a>(line: &'a str, ruleset: &Ruleset) -> IResult<&'a str, (Vec<Card>, usize)> {
+    let hand = count(map_opt(anychar, |label| ruleset.card(label)), ruleset.hand_size);
+
+    terminated(hand, space1).and(map_res(u32, usize::try_from)).parse(line)
+}
+
+/// Parses every line of the source data into a hand and its bid, dealt
+/// under the given ruleset.
+fn parse_hands(source: &str, ruleset: &Ruleset) -> Result<Vec<(Hand, usize)>, Error> {
+    let hands = lines(map(
+        |line| parse_hand_and_bid(line, ruleset),
+        |(cards, bid)| (ruleset.hand(cards), bid),
+    ));
+
+    all_consuming(terminated(hands, multispace0))
+        .parse(source)
+        .map(|(_, hands)| hands)
+        .map_err(|err| hand_error(source, err))
+}
+
+/// Converts a failure from [parse_hand_and_bid] into an [Error], naming
+/// invalid card labels rather than the combinator which rejected them.
+fn hand_error(source: &str, err: nom::Err<nom::error::Error<&str>>) -> Error {
+    match err {
+        nom::Err::Error(err) | nom::Err::Failure(err) if err.code == ErrorKind::MapOpt => {
+            Error::parse(source, err.input, "invalid card label")
+        }
+        err => Error::from_nom(source, err),
+    }
+}
+
+/// Every hand in the source data with its bid, parsed according
+/// to the rules of both questions.
+pub struct Hands {
+    /// The hands under the rules of question 1.
+    standard: Vec<(Hand, usize)>,
+    /// The hands under the rules of question 2, where J is a joker.
+    joker: Vec<(Hand, usize)>,
+}
+
+impl Hands {
+    /// The ruleset of the question, and the hands dealt under it.
+    fn for_question(&self, question: Question) -> (&Ruleset, &[(Hand, usize)]) {
+        match question {
+            Question::One => (&Ruleset::STANDARD, &self.standard),
+            Question::Two => (&Ruleset::JOKERS, &self.joker),
+        }
+    }
+}
+
+/// Sorts the given hands from weakest to strongest, and pairs each
+/// hand and its bid with its rank, counting from 1.
+fn ranked(hands: &[(Hand, usize)]) -> Vec<(usize, &Hand, usize)> {
+    let mut hands: Vec<_> = hands.iter().collect();
+    hands.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    hands
+        .into_iter()
+        .enumerate()
+        .map(|(rank, (hand, bid))| (rank + 1, hand, *bid))
+        .collect()
+}
+
+/// Ranks the given hands and returns the sum of their bids
+/// multiplied by their ranks.
+fn total_winnings(hands: &[(Hand, usize)]) -> usize {
+    ranked(hands).into_iter().map(|(rank, _, bid)| rank * bid).sum()
+}
+
+/// Lists every hand as CSV in order of rank, with the type it resolved
+/// to, the cards with each wildcard replaced by the card it stood in for,
+/// and the amount its bid contributes to the total winnings.
+fn explain_winnings(ruleset: &Ruleset, hands: &[(Hand, usize)]) -> String {
+    let mut table = String::from("rank,hand,type,resolved,bid,winnings");
+
+    for (rank, hand, bid) in ranked(hands) {
+        let dealt: String = hand.cards().iter().map(|&card| ruleset.label(card)).collect();
+        let resolved: String = hand
+            .cards()
+            .iter()
+            .map(|&card| match hand.wildcard_as() {
+                Some(wildcard_as) if ruleset.is_wild(card) => ruleset.label(wildcard_as),
+                _ => ruleset.label(card),
+            })
+            .collect();
+
+        table.push_str(&format!(
+            "\n{},{},{},{},{},{}",
+            rank,
+            dealt,
+            hand.hand_type(),
+            resolved,
+            bid,
+            rank * bid
+        ));
+    }
+
+    table
+}
+
+/// Day 7: Camel Cards
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    const DAY: u8 = 7;
+    const EXAMPLES: [&'static str; 2] = [include_str!("../../data/examples/day7.txt"); 2];
+    type Input = Hands;
+    type Answer = usize;
+
+    fn parse(source: &str) -> Result<Self::Input, Error> {
+        Ok(Hands {
+            standard: parse_hands(source, &Ruleset::STANDARD)?,
+            joker: parse_hands(source, &Ruleset::JOKERS)?,
+        })
+    }
+
+    fn part_one(input: &Self::Input) -> Result<usize, Error> {
+        Ok(total_winnings(&input.standard))
+    }
+
+    fn part_two(input: &Self::Input) -> Result<usize, Error> {
+        Ok(total_winnings(&input.joker))
+    }
+
+    fn explain(question: Question, input: &Self::Input) -> Result<Option<String>, Error> {
+        let (ruleset, hands) = input.for_question(question);
+        Ok(Some(explain_winnings(ruleset, hands)))
+    }
+}
+
+pub const DAY: Day = Day::of::<Puzzle>();
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cmp::Ordering;
+
+    /// Deals hands from only a few kinds of card, so that many of them
+    /// share a type, or tie on their first few cards, or are identical;
+    /// one of the kinds is always the weakest card, which is the joker
+    /// when there is one.
+    fn random_hands(rng: &mut fastrand::Rng, ruleset: &Ruleset) -> Vec<Hand> {
+        let mut kinds = vec![Card(0)];
+        kinds.extend((0..3).map(|_| Card(rng.u8(0..ruleset.labels.len() as u8))));
+        (0..40)
+            .map(|_| ruleset.hand((0..ruleset.hand_size).map(|_| kinds[rng.usize(..kinds.len())]).collect()))
+            .collect()
+    }
+
+    fn check_ordering(ruleset: &Ruleset, seed: u64) {
+        let mut rng = fastrand::Rng::with_seed(seed);
+        for _ in 0..20 {
+            let hands = random_hands(&mut rng, ruleset);
+            for a in &hands {
+                for b in &hands {
+                    assert_eq!(a.cmp(b), b.cmp(a).reverse(), "{:?} and {:?}", a, b);
+                    assert_eq!(a == b, a.cmp(b) == Ordering::Equal, "{:?} and {:?}", a, b);
+                    assert_eq!(a.partial_cmp(b), Some(a.cmp(b)), "{:?} and {:?}", a, b);
+                    for c in &hands {
+                        if a <= b && b <= c {
+                            assert!(a <= c, "{:?}, {:?} and {:?}", a, b, c);
+                        }
+                        if a < b && b < c {
+                            assert!(a < c, "{:?}, {:?} and {:?}", a, b, c);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn example() {
+        let input = Puzzle::parse(Puzzle::EXAMPLES[0]).unwrap();
+        assert_eq!(Puzzle::part_one(&input).unwrap(), 6440);
+        assert_eq!(Puzzle::part_two(&input).unwrap(), 5905);
+    }
+
+    fn hand(ruleset: &Ruleset, labels: &str) -> Hand {
+        ruleset.hand(labels.chars().map(|label| ruleset.card(label).unwrap()).collect())
+    }
+
+    #[test]
+    fn jokers_join_the_most_common_card() {
+        let ruleset = Ruleset::JOKERS;
+        let cases = [
+            ("KTJJT", HandType::FourOfAKind, Some('T')),
+            ("JJJJJ", HandType::FiveOfAKind, Some('A')),
+            ("2345J", HandType::OnePair, Some('5')),
+            ("2233J", HandType::FullHouse, Some('3')),
+            ("QQQJA", HandType::FourOfAKind, Some('Q')),
+            ("T55J5", HandType::FourOfAKind, Some('5')),
+            ("32T3K", HandType::OnePair, None),
+        ];
+
+        for (labels, hand_type, wildcard_as) in cases {
+            let hand = hand(&ruleset, labels);
+            assert_eq!(hand.hand_type(), hand_type, "{}", labels);
+            assert_eq!(hand.wildcard_as().map(|card| ruleset.label(card)), wildcard_as, "{}", labels);
+        }
+    }
+
+    #[test]
+    fn jokers_make_the_strongest_type_of_any_classifier() {
+        // two pair beats everything here, so a joker in a hand with a pair
+        // is better off making a second pair than three of a kind
+        let ruleset = Ruleset {
+            classify: |counts| match counts {
+                [2, 2, ..] => HandType::FiveOfAKind,
+                counts => classify_by_counts(counts),
+            },
+            ..Ruleset::JOKERS
+        };
+
+        let hand = hand(&ruleset, "AAKQJ");
+        assert_eq!(hand.hand_type(), HandType::FiveOfAKind);
+        assert_eq!(hand.wildcard_as().map(|card| ruleset.label(card)), Some('K'));
+    }
+
+    #[test]
+    fn standard_hands_are_totally_ordered() {
+        check_ordering(&Ruleset::STANDARD, 7);
+    }
+
+    #[test]
+    fn joker_hands_are_totally_ordered() {
+        check_ordering(&Ruleset::JOKERS, 77);
+    }
+}
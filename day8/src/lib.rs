@@ -0,0 +1,768 @@
+use std::{collections::HashMap, fmt::Write};
+
+use aoc::{
+    math::{crt, find_cycle, lcm, Cycle},
+    parse::{blank_line, finish, lines},
+    Day, Error, Question, Solver,
+};
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alphanumeric1, one_of},
+    combinator::map,
+    multi::many1,
+    sequence::{separated_pair, terminated, Tuple},
+    IResult, Parser,
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+/// A node in a [Network], identified by the order in which its
+/// name was first seen.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+struct Node(u32);
+
+/// A network of nodes with names of any length, each of which leads to
+/// a node on its left and a node on its right.
+#[derive(Debug, Clone, Default)]
+pub struct Network {
+    /// The name of each node, indexed by the node.
+    names: Vec<String>,
+    /// The node with each name.
+    nodes: HashMap<String, Node>,
+    /// The nodes on the left and right of each node, indexed by the node.
+    edges: Vec<[u32; 2]>,
+}
+
+/// The nodes reached by following a path through a [Network] a power of two
+/// times over, and by following each part of the path from its beginning,
+/// so that walking any number of steps along the path takes a number of
+/// lookups logarithmic in the number of steps.
+#[derive(Debug, Clone)]
+struct Jumps<'a> {
+    path: &'a [Side],
+    /// The node reached from each node by following the whole path 2^k
+    /// times, for each k.
+    laps: Vec<Vec<u32>>,
+    /// The node reached from each node by following the first i steps of
+    /// the path, for each i less than the length of the path.
+    prefixes: Vec<Vec<u32>>,
+    /// The steps during a single lap of the path from each node at which
+    /// the walk stands on an end node.
+    hits: Vec<Vec<usize>>,
+}
+
+impl<'a> Jumps<'a> {
+    /// Precomputes the jumps along the path through the network, for walks
+    /// of any number of steps which fits in a [usize], along with where
+    /// each lap meets the given end nodes.
+    fn new(network: &'a Network, path: &'a [Side], is_end: &[bool]) -> Self {
+        // follow the whole path once from every node, moving every node
+        // along each step together
+        let mut lap = (0..network.edges.len() as u32).collect::<Vec<_>>();
+        let mut hits = vec![Vec::new(); lap.len()];
+        let mut prefixes = Vec::with_capacity(path.len());
+        for (steps, &side) in path.iter().enumerate() {
+            prefixes.push(lap.clone());
+            for (start, node) in lap.iter_mut().enumerate() {
+                if is_end[*node as usize] {
+                    hits[start].push(steps);
+                }
+                *node = network.step(Node(*node), side).0;
+            }
+        }
+
+        let levels = (usize::BITS - (usize::MAX / path.len()).leading_zeros()) as usize;
+        let mut laps = Vec::with_capacity(levels);
+        laps.push(lap);
+        while laps.len() < levels {
+            let last = laps.last().expect("starts with one lap");
+            let doubled = last.iter().map(|&node| last[node as usize]).collect();
+            laps.push(doubled);
+        }
+
+        Jumps {
+            path,
+            laps,
+            prefixes,
+            hits,
+        }
+    }
+
+    /// Returns the node reached by walking the given number of steps from
+    /// the start node, starting from the beginning of the path, with one
+    /// lookup for each bit in the number of whole laps and one for the rest.
+    fn walk(&self, start: Node, steps: usize) -> Node {
+        let (laps, rest) = (steps / self.path.len(), steps % self.path.len());
+
+        let node = self
+            .laps
+            .iter()
+            .enumerate()
+            .filter(|(k, _)| laps >> k & 1 == 1)
+            .fold(start, |node, (_, level)| Node(level[node.0 as usize]));
+
+        Node(self.prefixes[rest][node.0 as usize])
+    }
+}
+
+/// The outcome of following a path through the network until it
+/// reaches an end node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Traversal {
+    /// An end node was reached after the given number of steps.
+    Reached(usize),
+    /// The start node isn't in the network, or no node in the network is
+    /// an end node, so there's nothing to walk towards.
+    Unreachable,
+    /// The walk went round in circles without reaching an end node: after
+    /// `prefix` steps, its node and position in the path repeat every
+    /// `period` steps from then on.
+    Cycle { prefix: usize, period: usize },
+}
+
+/// How to draw a [Network] with [Network::to_dot].
+#[derive(Debug, Clone, Copy)]
+pub struct DotOptions {
+    /// Whether a node is a start node, given its name.
+    pub start: fn(&str) -> bool,
+    /// Whether a node is an end node, given its name.
+    pub end: fn(&str) -> bool,
+    /// Whether to fill every node which can be reached from a start node
+    /// with a colour for that start node.
+    pub components: bool,
+}
+
+/// The colours which the components reachable from each start node are
+/// filled with, in turn.
+const COMPONENT_COLOURS: [&str; 8] = [
+    "lightblue",
+    "lightpink",
+    "palegreen",
+    "khaki",
+    "plum",
+    "lightsalmon",
+    "aquamarine",
+    "wheat",
+];
+
+/// Where a single ghost stands on an end node as it follows the path
+/// forever, found from the cycle in its state, which is its node along
+/// with its position in the path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Orbit {
+    /// The shape of the sequence of states.
+    cycle: Cycle,
+    /// The steps before the ghost enters the cycle at which it stands on
+    /// an end node.
+    transient: Vec<usize>,
+    /// The steps during its first lap of the cycle at which the ghost
+    /// stands on an end node, which recur every lap.
+    periodic: Vec<usize>,
+}
+
+impl Orbit {
+    /// Whether the ghost stands on an end node after the given number of steps.
+    fn at_end(&self, steps: usize) -> bool {
+        if steps < self.cycle.start {
+            self.transient.contains(&steps)
+        } else {
+            self.periodic.contains(&self.cycle.reduce(steps))
+        }
+    }
+}
+
+impl Network {
+    /// Returns the node with the given name, adding it to the network
+    /// if it isn't there already.
+    fn intern(&mut self, name: &str) -> Node {
+        if let Some(&node) = self.nodes.get(name) {
+            return node;
+        }
+
+        let node = Node(u32::try_from(self.names.len()).expect("fewer than 2^32 nodes"));
+        self.names.push(name.to_string());
+        self.nodes.insert(name.to_string(), node);
+        node
+    }
+
+    /// Returns the node with the given name, if there is one.
+    fn node(&self, name: &str) -> Option<Node> {
+        self.nodes.get(name).copied()
+    }
+
+    /// Returns the name of the node.
+    fn name(&self, node: Node) -> &str {
+        &self.names[node.0 as usize]
+    }
+
+    /// Iterates over every node in the network whose name satisfies the
+    /// predicate, in the order they were first seen.
+    fn nodes_named<'a>(&'a self, predicate: impl Fn(&str) -> bool + 'a) -> impl Iterator<Item = Node> + 'a {
+        (0..self.names.len() as u32)
+            .map(Node)
+            .filter(move |&node| predicate(self.name(node)))
+    }
+
+    /// Returns the node reached by taking the given side from the node.
+    fn step(&self, node: Node, side: Side) -> Node {
+        let [left, right] = self.edges[node.0 as usize];
+        match side {
+            Side::Left => Node(left),
+            Side::Right => Node(right),
+        }
+    }
+
+    /// Whether each node, indexed by the node, has a name which satisfies
+    /// the predicate, so that walks needn't look at names at every step.
+    fn named(&self, predicate: impl Fn(&str) -> bool) -> Vec<bool> {
+        self.names.iter().map(|name| predicate(name)).collect()
+    }
+
+    /// Follows the path from the start node forever, starting from the
+    /// beginning of the path, and finds the steps at which it stands on
+    /// one of the end nodes which the jumps were computed for.
+    fn orbit(&self, jumps: &Jumps, start: Node) -> Orbit {
+        let path = jumps.path;
+
+        // the state can only repeat at the same position in the path, so the
+        // cycle in the state is a whole number of laps around the cycle in
+        // the nodes reached at the start of each lap
+        let laps = find_cycle(start, |node| Node(jumps.laps[0][node.0 as usize]));
+        let length = laps.length * path.len();
+
+        // but it can start partway through the lap before, at the first step
+        // after which the state matches the state a cycle later
+        let mut cycle_start = 0;
+        if laps.start > 0 {
+            let before = (laps.start - 1) * path.len();
+            let walk = |node: Node| {
+                path.iter().scan(node, |node, &side| {
+                    *node = self.step(*node, side);
+                    Some(*node)
+                })
+            };
+            let matching = walk(jumps.walk(start, before))
+                .zip(walk(jumps.walk(start, before + length)))
+                .take_while(|(a, b)| a != b)
+                .count();
+            cycle_start = before + matching + 1;
+        }
+        let cycle = Cycle {
+            start: cycle_start,
+            length,
+        };
+
+        // the first lap of the cycle ends by the time the nodes at the start
+        // of each lap have gone round their cycle once, so only those laps
+        // need to be visited
+        let mut transient = Vec::new();
+        let mut periodic = Vec::new();
+        let mut node = start;
+        for lap in 0..laps.start + laps.length {
+            for &offset in &jumps.hits[node.0 as usize] {
+                let steps = lap * path.len() + offset;
+                if steps < cycle.start {
+                    transient.push(steps);
+                } else if steps < cycle.start + cycle.length {
+                    periodic.push(steps);
+                }
+            }
+            node = Node(jumps.laps[0][node.0 as usize]);
+        }
+
+        Orbit {
+            cycle,
+            transient,
+            periodic,
+        }
+    }
+
+    /// Follows the path from the node with the given name, starting from the
+    /// beginning of the path and going back to it whenever it runs out, until
+    /// it reaches a node whose name satisfies `end`, which takes at least
+    /// one step.
+    ///
+    /// The walk can only be in as many states as there are pairs of nodes
+    /// and positions in the path, so it stops as soon as a state repeats.
+    fn traverse(&self, path: &[Side], start: &str, end: impl Fn(&str) -> bool) -> Traversal {
+        let Some(start) = self.node(start) else {
+            return Traversal::Unreachable;
+        };
+        if self.nodes_named(&end).next().is_none() {
+            return Traversal::Unreachable;
+        }
+
+        // the step at which each state was first seen, which only grows
+        // as far as the walk goes
+        let is_end = self.named(end);
+        let mut seen = HashMap::new();
+        let (mut node, mut index) = (start, 0);
+        let mut steps = 0;
+
+        loop {
+            if let Some(&first) = seen.get(&(node, index)) {
+                return Traversal::Cycle {
+                    prefix: first,
+                    period: steps - first,
+                };
+            }
+            seen.insert((node, index), steps);
+
+            node = self.step(node, path[index]);
+            index = if index + 1 == path.len() { 0 } else { index + 1 };
+            steps += 1;
+
+            if is_end[node.0 as usize] {
+                return Traversal::Reached(steps);
+            }
+        }
+    }
+
+    /// Traverses the network in lockstep, starting from every node whose
+    /// name satisfies `start`, and returns the number of steps after which
+    /// every ghost stands on a node whose name satisfies `end`.
+    ///
+    /// Each ghost starts at the beginning of the path, and its state (its
+    /// node and position in the path) must eventually repeat, so once all
+    /// the ghosts are in their cycles, each stands on an end node at a fixed
+    /// set of residues modulo its cycle length. The ghosts are merged one
+    /// at a time with the chinese remainder theorem, keeping the distinct
+    /// residues modulo the lcm of the cycle lengths so far at which every
+    /// ghost merged so far stands on an end node.
+    fn ghost_traverse(
+        &self,
+        path: &[Side],
+        start: impl Fn(&str) -> bool,
+        end: impl Fn(&str) -> bool,
+    ) -> Result<usize, Error> {
+        let starts = self.nodes_named(start).collect::<Vec<_>>();
+        if starts.is_empty() {
+            return Err(Error::NoAnswer(String::from("no start nodes")));
+        }
+
+        let is_end = self.named(end);
+        let jumps = Jumps::new(self, path, &is_end);
+        let orbits = starts
+            .iter()
+            .map(|&node| self.orbit(&jumps, node))
+            .collect::<Vec<_>>();
+
+        let overflow = || Error::NoAnswer(String::from("the number of steps overflows"));
+        let never = || Error::NoAnswer(String::from("the ghosts never all reach an end node at once"));
+
+        // until every ghost is in its cycle, there's nothing to solve
+        let settled = orbits.iter().map(|orbit| orbit.cycle.start).max().unwrap_or(0).max(1);
+        if let Some(steps) = (1..settled).find(|&steps| orbits.iter().all(|orbit| orbit.at_end(steps))) {
+            return Ok(steps);
+        }
+
+        let (mut residues, mut period) = (vec![0u64], 1u64);
+        for orbit in &orbits {
+            let length = orbit.cycle.length as u64;
+            let modulus = lcm(period, length).ok_or_else(overflow)?;
+
+            // the modulus is representable, so this only fails to solve the
+            // pairs of congruences which contradict one another
+            let mut merged = residues
+                .iter()
+                .flat_map(|&residue| {
+                    orbit
+                        .periodic
+                        .iter()
+                        .filter_map(move |&steps| crt(&[(residue, period), (steps as u64 % length, length)]))
+                })
+                .map(|(residue, _)| residue)
+                .collect::<Vec<_>>();
+            merged.sort_unstable();
+            merged.dedup();
+
+            (residues, period) = (merged, modulus);
+            if residues.is_empty() {
+                return Err(never());
+            }
+        }
+        let period = usize::try_from(period).map_err(|_| overflow())?;
+
+        // the least step congruent to any residue at which every ghost is
+        // in its cycle, of those which are representable
+        let steps = residues
+            .into_iter()
+            .filter_map(|residue| {
+                let residue = residue as usize;
+                if residue >= settled {
+                    Some(residue)
+                } else {
+                    (settled - residue).div_ceil(period).checked_mul(period)?.checked_add(residue)
+                }
+            })
+            .min()
+            .ok_or_else(overflow)?;
+
+        // walking straight there must leave every ghost on an end node
+        debug_assert!(starts.iter().all(|&node| is_end[jumps.walk(node, steps).0 as usize]));
+
+        Ok(steps)
+    }
+
+    /// Writes the network as a directed graph in the DOT language of
+    /// Graphviz, with an edge to the left and right of every node which
+    /// is labelled L or R (or LR, if both sides lead to the same node),
+    /// and with start nodes outlined in green and end nodes in red.
+    ///
+    /// When the options ask for components, each node is filled with the
+    /// colour of the first start node it can be reached from.
+    pub fn to_dot(&self, options: &DotOptions) -> String {
+        let mut component = vec![None; self.names.len()];
+        if options.components {
+            for (colour, start) in self.nodes_named(options.start).enumerate() {
+                let mut stack = vec![start];
+                while let Some(node) = stack.pop() {
+                    if component[node.0 as usize].is_some() {
+                        continue;
+                    }
+
+                    component[node.0 as usize] = Some(colour % COMPONENT_COLOURS.len());
+                    let [left, right] = self.edges[node.0 as usize];
+                    stack.extend([Node(left), Node(right)]);
+                }
+            }
+        }
+
+        let mut dot = String::from("digraph network {\n");
+        for node in self.nodes_named(|_| true) {
+            let name = self.name(node);
+            let mut attributes = Vec::new();
+
+            if (options.start)(name) {
+                attributes.push(String::from("shape=doublecircle, color=green"));
+            } else if (options.end)(name) {
+                attributes.push(String::from("shape=doublecircle, color=red"));
+            }
+            if let Some(colour) = component[node.0 as usize] {
+                attributes.push(format!("style=filled, fillcolor={}", COMPONENT_COLOURS[colour]));
+            }
+
+            if attributes.is_empty() {
+                writeln!(dot, "    \"{}\";", name)
+            } else {
+                writeln!(dot, "    \"{}\" [{}];", name, attributes.join(", "))
+            }
+            .expect("writing to a string can't fail");
+        }
+
+        for node in self.nodes_named(|_| true) {
+            let [left, right] = self.edges[node.0 as usize].map(Node);
+            let edges = if left == right {
+                vec![(left, "LR")]
+            } else {
+                vec![(left, "L"), (right, "R")]
+            };
+
+            for (target, label) in edges {
+                writeln!(dot, "    \"{}\" -> \"{}\" [label={}];", self.name(node), self.name(target), label)
+                    .expect("writing to a string can't fail");
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Parses the first line of the source data.
+fn path(source: &str) -> IResult<&str, Vec<Side>> {
+    let mut parser = many1(map(one_of("RL"), |side| match side {
+        'R' => Side::Right,
+        'L' => Side::Left,
+        _ => unreachable!(),
+    }));
+
+    parser.parse(source)
+}
+
+/// Parses the name of a node, which may be made of any letters and digits.
+fn name(source: &str) -> IResult<&str, &str> {
+    alphanumeric1(source)
+}
+
+/// Parses a line from the "network" section of the source data into the
+/// names of a node and the nodes on its left and right.
+fn mapping(source: &str) -> IResult<&str, (&str, (&str, &str))> {
+    let mut parser = (
+        terminated(name, tag(" = (")),
+        terminated(name, tag(", ")),
+        terminated(name, tag(")")),
+    );
+    let (tail, (source, left, right)) = parser.parse(source)?;
+    Ok((tail, (source, (left, right))))
+}
+
+/// Parses the source data into a path and the network it traverses, in
+/// which every node that a mapping leads to must have a mapping of its own,
+/// and no node may have more than one.
+fn parse_input_data(source: &str) -> Result<(Vec<Side>, Network), Error> {
+    let (path, mappings) = finish(source, separated_pair(path, blank_line, lines(mapping)))?;
+
+    let mut network = Network::default();
+    for &(node, _) in &mappings {
+        if network.node(node).is_some() {
+            return Err(Error::parse(source, node, format!("node {} is mapped twice", node)));
+        }
+        network.intern(node);
+    }
+
+    // every node with a mapping has been seen, so any other name is a dead end
+    network.edges = vec![[0, 0]; network.names.len()];
+    for (node, (left, right)) in mappings {
+        let target = |name: &str| {
+            network
+                .node(name)
+                .ok_or_else(|| Error::parse(source, name, format!("node {} has no mapping", name)))
+        };
+        let edges = [target(left)?.0, target(right)?.0];
+        let node = network.node(node).expect("interned above");
+        network.edges[node.0 as usize] = edges;
+    }
+
+    Ok((path, network))
+}
+
+/// Day 8: Haunted Wasteland
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    const DAY: u8 = 8;
+    const EXAMPLES: [&'static str; 2] = [
+        include_str!("../../data/examples/day8.txt"),
+        include_str!("../../data/examples/day8-2.txt"),
+    ];
+    type Input = (Vec<Side>, Network);
+    type Answer = usize;
+
+    fn parse(source: &str) -> Result<Self::Input, Error> {
+        parse_input_data(source)
+    }
+
+    fn part_one((path, network): &Self::Input) -> Result<usize, Error> {
+        match network.traverse(path, "AAA", |name| name == "ZZZ") {
+            Traversal::Reached(steps) => Ok(steps),
+            Traversal::Unreachable => Err(Error::NoAnswer(String::from("the network has no node AAA or no node ZZZ"))),
+            Traversal::Cycle { prefix, period } => Err(Error::NoAnswer(format!(
+                "the path from AAA never reaches ZZZ; after {} steps it repeats every {} steps",
+                prefix, period
+            ))),
+        }
+    }
+
+    fn part_two((path, network): &Self::Input) -> Result<usize, Error> {
+        network.ghost_traverse(path, |name| name.ends_with('A'), |name| name.ends_with('Z'))
+    }
+
+    fn dot(question: Question, (_, network): &Self::Input) -> Result<Option<String>, Error> {
+        let options = match question {
+            Question::One => DotOptions {
+                start: |name| name == "AAA",
+                end: |name| name == "ZZZ",
+                components: false,
+            },
+            Question::Two => DotOptions {
+                start: |name| name.ends_with('A'),
+                end: |name| name.ends_with('Z'),
+                components: true,
+            },
+        };
+
+        Ok(Some(network.to_dot(&options)))
+    }
+}
+
+pub const DAY: Day = Day::of::<Puzzle>();
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        let input = Puzzle::parse(Puzzle::EXAMPLES[0]).unwrap();
+        assert_eq!(Puzzle::part_one(&input).unwrap(), 6);
+        let input = Puzzle::parse(Puzzle::EXAMPLES[1]).unwrap();
+        assert_eq!(Puzzle::part_two(&input).unwrap(), 6);
+    }
+
+    #[test]
+    fn dot() {
+        let input = Puzzle::parse(Puzzle::EXAMPLES[0]).unwrap();
+        let graph = Puzzle::dot(Question::One, &input).unwrap().unwrap();
+        assert!(graph.starts_with("digraph network {\n"));
+        assert!(graph.ends_with("}\n"));
+        assert!(graph.contains("    \"AAA\" [shape=doublecircle, color=green];\n"));
+        assert!(graph.contains("    \"BBB\" -> \"ZZZ\" [label=R];\n"));
+    }
+
+    #[test]
+    fn node_mapped_twice() {
+        let source = "L\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\nAAA = (AAA, AAA)\n";
+        match Puzzle::parse(source) {
+            Err(Error::Parse(diagnostic)) => {
+                assert_eq!(diagnostic.message, "node AAA is mapped twice");
+                assert_eq!((diagnostic.line, diagnostic.column), (5, 1));
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    /// Writes the source data for a small random network, in which many
+    /// walks enter their cycles partway through the path and pass several
+    /// end nodes on each lap.
+    fn random_source(rng: &mut fastrand::Rng) -> String {
+        // there are between one and three ghosts, to keep the lockstep short
+        let size = rng.usize(1..10);
+        let names = (0..size)
+            .map(|i| match rng.u8(0..10) {
+                _ if i == 0 => format!("{}A", i),
+                0..=2 if i < 3 => format!("{}A", i),
+                3..=6 => format!("{}Z", i),
+                _ => format!("{}B", i),
+            })
+            .collect::<Vec<_>>();
+
+        let path = (0..rng.usize(1..5)).map(|_| if rng.bool() { 'L' } else { 'R' }).collect::<String>();
+        let mut source = format!("{}\n\n", path);
+        for name in &names {
+            let (left, right) = (&names[rng.usize(..size)], &names[rng.usize(..size)]);
+            source.push_str(&format!("{} = ({}, {})\n", name, left, right));
+        }
+
+        source
+    }
+
+    /// Steps every ghost together until they all stand on end nodes at once,
+    /// for as long as it takes for the state of all the ghosts to repeat.
+    fn lockstep((path, network): &(Vec<Side>, Network)) -> Option<usize> {
+        let is_end = network.named(|name| name.ends_with('Z'));
+        let mut ghosts = network.nodes_named(|name| name.ends_with('A')).collect::<Vec<_>>();
+        if ghosts.is_empty() {
+            return None;
+        }
+
+        let states = network.names.len().pow(ghosts.len() as u32) * path.len();
+        (1..=states).find(|steps| {
+            let side = path[(steps - 1) % path.len()];
+            for ghost in &mut ghosts {
+                *ghost = network.step(*ghost, side);
+            }
+            ghosts.iter().all(|ghost| is_end[ghost.0 as usize])
+        })
+    }
+
+    #[test]
+    fn jumps_walk_matches_stepping() {
+        let mut rng = fastrand::Rng::with_seed(25);
+        for _ in 0..200 {
+            let (path, network) = Puzzle::parse(&random_source(&mut rng)).unwrap();
+            let is_end = network.named(|name| name.ends_with('Z'));
+            let jumps = Jumps::new(&network, &path, &is_end);
+
+            for start in network.nodes_named(|_| true) {
+                let mut node = start;
+                for steps in 0..300 {
+                    assert_eq!(jumps.walk(start, steps), node, "{} steps from {:?}", steps, start);
+                    node = network.step(node, path[steps % path.len()]);
+                }
+
+                // far longer walks are made of shorter walks of whole laps
+                let (a, b) = (rng.usize(..1 << 40), rng.usize(..1 << 40));
+                let (a, b) = (a * path.len(), b * path.len() + rng.usize(..path.len()));
+                assert_eq!(jumps.walk(start, a + b), jumps.walk(jumps.walk(start, a), b));
+            }
+        }
+    }
+
+    #[test]
+    fn ghosts_match_lockstep() {
+        let mut rng = fastrand::Rng::with_seed(8);
+        for _ in 0..3000 {
+            let source = random_source(&mut rng);
+            let input = Puzzle::parse(&source).unwrap();
+            assert_eq!(Puzzle::part_two(&input).ok(), lockstep(&input), "{}", source);
+        }
+    }
+
+    #[test]
+    fn ghosts_with_many_end_nodes() {
+        // a ghost on a cycle of each prime length, with 17 end nodes on
+        // each cycle, for 17^4 choices of end nodes
+        let mut rng = fastrand::Rng::with_seed(17);
+        let mut source = String::from("L\n\n");
+        for (ghost, length) in [37, 41, 43, 47].into_iter().enumerate() {
+            let mut ends = vec![false; length];
+            for end in rng.choose_multiple(1..length, 17) {
+                ends[end] = true;
+            }
+            let name = |i: usize| {
+                let kind = if i == 0 { 'A' } else if ends[i] { 'Z' } else { 'B' };
+                format!("{}x{}{}", ghost, i, kind)
+            };
+            for i in 0..length {
+                let next = name((i + 1) % length);
+                source.push_str(&format!("{} = ({}, {})\n", name(i), next, next));
+            }
+        }
+
+        let input = Puzzle::parse(&source).unwrap();
+        assert_eq!(Puzzle::part_two(&input).ok(), lockstep(&input));
+    }
+
+    #[test]
+    fn traverse_reaches_the_end() {
+        let input = Puzzle::parse(Puzzle::EXAMPLES[0]).unwrap();
+        assert_eq!(input.1.traverse(&input.0, "AAA", |name| name == "ZZZ"), Traversal::Reached(6));
+        // the start node doesn't count as reaching the end
+        assert_eq!(input.1.traverse(&input.0, "ZZZ", |name| name == "ZZZ"), Traversal::Reached(1));
+    }
+
+    #[test]
+    fn traverse_cycle_which_misses_the_end() {
+        // after the first step the walk swaps between BBB and CCC, which only
+        // repeats at the same position in the path of 3 steps every 6 steps
+        let source = "LLR\n\nAAA = (BBB, ZZZ)\nBBB = (CCC, CCC)\nCCC = (BBB, BBB)\nZZZ = (ZZZ, ZZZ)\n";
+        let input = Puzzle::parse(source).unwrap();
+        assert_eq!(
+            input.1.traverse(&input.0, "AAA", |name| name == "ZZZ"),
+            Traversal::Cycle { prefix: 1, period: 6 }
+        );
+        assert_eq!(
+            Puzzle::part_one(&input),
+            Err(Error::NoAnswer(String::from(
+                "the path from AAA never reaches ZZZ; after 1 steps it repeats every 6 steps"
+            )))
+        );
+    }
+
+    #[test]
+    fn traverse_without_a_start_or_an_end() {
+        let unreachable = Err(Error::NoAnswer(String::from("the network has no node AAA or no node ZZZ")));
+
+        let input = Puzzle::parse("L\n\nBBB = (ZZZ, ZZZ)\nZZZ = (BBB, BBB)\n").unwrap();
+        assert_eq!(input.1.traverse(&input.0, "AAA", |name| name == "ZZZ"), Traversal::Unreachable);
+        assert_eq!(Puzzle::part_one(&input), unreachable);
+
+        let input = Puzzle::parse("L\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\n").unwrap();
+        assert_eq!(input.1.traverse(&input.0, "AAA", |name| name == "ZZZ"), Traversal::Unreachable);
+        assert_eq!(Puzzle::part_one(&input), unreachable);
+    }
+
+    #[test]
+    fn no_start_nodes() {
+        let input = Puzzle::parse("LR\n\nBBB = (BBB, BBB)\n").unwrap();
+        assert_eq!(
+            Puzzle::part_two(&input),
+            Err(Error::NoAnswer(String::from("no start nodes")))
+        );
+    }
+}
@@ -0,0 +1,95 @@
+use aoc::{parse::{finish, integers, lines}, Day, Error, Solver};
+use nom::combinator::map;
+
+/// Represents a single line from the source data.
+pub struct History {
+    /// The literal values of a source data line.
+    sequence: Vec<isize>,
+}
+
+impl History {
+    /// Returns the trailing edge of the difference stack,
+    /// such that the sum of the resulting vector is the
+    /// extrapolated value as described in question 1.
+    fn get_diff_stack_trailing_edge(&self) -> Vec<isize> {
+        let mut edge = Vec::new();
+        let mut derivative = self.sequence.clone();
+
+        while derivative.iter().filter(|&&x| x != 0).count() != 0 {
+            edge.push(*derivative.last().unwrap());
+            derivative = diff(derivative);
+        }
+
+        edge
+    }
+
+    /// Returns the leading edge of the difference stack,
+    /// such that the application of reduce(|acc, x| x - acc) (in reverse)
+    /// yields the extrapolated value as described in question 2.
+    fn get_diff_stack_leading_edge(&self) -> Vec<isize> {
+        let mut edge = Vec::new();
+        let mut derivative = self.sequence.clone();
+
+        while derivative.iter().filter(|&&x| x != 0).count() != 0 {
+            edge.push(*derivative.first().unwrap());
+            derivative = diff(derivative);
+        }
+
+        edge
+    }
+}
+
+/// Computes the first difference of the given vector.
+fn diff(vec: Vec<isize>) -> Vec<isize> {
+    vec.windows(2).map(|w| w[1] - w[0]).collect::<Vec<_>>()
+}
+
+/// Day 9: Mirage Maintenance
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    const DAY: u8 = 9;
+    const EXAMPLES: [&'static str; 2] = [include_str!("../../data/examples/day9.txt"); 2];
+    type Input = Vec<History>;
+    type Answer = isize;
+
+    fn parse(source: &str) -> Result<Self::Input, Error> {
+        finish(source, lines(map(integers, |sequence| History { sequence })))
+    }
+
+    fn part_one(input: &Self::Input) -> Result<isize, Error> {
+        Ok(input
+            .iter()
+            .map(|history| history.get_diff_stack_trailing_edge().into_iter().sum::<isize>())
+            .sum())
+    }
+
+    fn part_two(input: &Self::Input) -> Result<isize, Error> {
+        Ok(input
+            .iter()
+            .map(|history| history.get_diff_stack_leading_edge())
+            .map(|edge| edge.into_iter().rev().reduce(|acc, x| x - acc).unwrap_or(0))
+            .sum())
+    }
+}
+
+pub const DAY: Day = Day::of::<Puzzle>();
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = Puzzle::parse(Puzzle::EXAMPLES[0]).unwrap();
+        assert_eq!(Puzzle::part_one(&input).unwrap(), 114);
+        assert_eq!(Puzzle::part_two(&input).unwrap(), 2);
+    }
+
+    #[test]
+    fn all_zero_history() {
+        let input = Puzzle::parse("0 0 0\n").unwrap();
+        assert_eq!(Puzzle::part_one(&input).unwrap(), 0);
+        assert_eq!(Puzzle::part_two(&input).unwrap(), 0);
+    }
+}
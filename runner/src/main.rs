@@ -0,0 +1,229 @@
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use aoc::{
+    answers::{Answers, Check},
+    output::Format,
+    source::Source,
+    Day, Question,
+};
+use argh::FromArgs;
+
+/// Every day that has been solved so far, in order.
+const DAYS: [Day; 9] = [
+    day1::DAY,
+    day2::DAY,
+    day3::DAY,
+    day4::DAY,
+    day5::DAY,
+    day6::DAY,
+    day7::DAY,
+    day8::DAY,
+    day9::DAY,
+];
+
+#[derive(FromArgs)]
+/// Runs the AOC2023 solutions.
+struct Cli {
+    #[argh(subcommand)]
+    command: Command,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+enum Command {
+    Run(Run),
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "run")]
+/// Run a single day, or every day, with input from data/dayN.txt
+/// unless another source is given.
+struct Run {
+    #[argh(positional)]
+    /// the day to run, optionally followed by the question
+    target: Vec<String>,
+    #[argh(switch)]
+    /// run every question of every day
+    all: bool,
+    #[argh(option, short = 'i')]
+    /// read the source data from the given path (or from stdin if
+    /// the path is -) instead of from data/dayN.txt
+    input: Option<PathBuf>,
+    #[argh(switch, short = 'e')]
+    /// use the example inputs from the puzzle descriptions
+    example: bool,
+    #[argh(switch)]
+    /// check the answers against the answers recorded in answers.toml
+    check: bool,
+    #[argh(option)]
+    /// time parsing and each question over the given number of iterations
+    bench: Option<usize>,
+    #[argh(option, short = 'o', default = "Format::Text")]
+    /// print the answers as text (the default) or as lines of json
+    output: Format,
+    #[argh(option)]
+    /// answer with the given strategy, for days which have more than one
+    strategy: Option<String>,
+    #[argh(switch)]
+    /// print how each answer was reached to stderr, for days which
+    /// can explain it
+    explain: bool,
+    #[argh(option)]
+    /// write the input as a Graphviz graph for the question to the given
+    /// path, for days which can draw it
+    dot: Option<PathBuf>,
+}
+
+/// Looks up the given day in the registry.
+fn find_day(number: u8) -> anyhow::Result<Day> {
+    DAYS.into_iter()
+        .find(|day| day.number == number)
+        .ok_or_else(|| anyhow!("day {} has not been solved", number))
+}
+
+/// Times parsing and the given questions of the given day with the named
+/// strategy, and prints the results.
+fn bench(
+    day: &Day,
+    source: &Source,
+    iterations: usize,
+    questions: &[Question],
+    strategy: Option<&str>,
+) -> anyhow::Result<()> {
+    day.check_strategy(strategy)?;
+    let [one, two] = source.read_all(day)?;
+    println!("day {}:", day.number);
+    println!("{}", (day.bench)([&one, &two], iterations, questions, strategy)?);
+    Ok(())
+}
+
+fn bench_all(args: &Run, iterations: usize) -> anyhow::Result<()> {
+    for day in DAYS {
+        let source = Source::from_args(None, args.example, day.number)?;
+        bench(&day, &source, iterations, &[Question::One, Question::Two], None)?;
+    }
+
+    Ok(())
+}
+
+fn run_all(args: &Run) -> anyhow::Result<()> {
+    let answers = if args.check { Some(Answers::load()?) } else { None };
+    let mut failures = 0;
+
+    for day in DAYS {
+        let source = Source::from_args(None, args.example, day.number)?;
+
+        for question in [Question::One, Question::Two] {
+            let outcome = day.run(question, &source, None)?;
+            match args.output {
+                Format::Text => println!("day {} question {}: {}", day.number, question, outcome.answer),
+                format => println!("{}", outcome.render(format)),
+            }
+
+            if let Some(answers) = &answers {
+                let check = answers.check(day.number, question, &source, &outcome.answer);
+                if matches!(check, Check::Fail { .. }) {
+                    failures += 1;
+                }
+
+                if let Err(err) = check.into_result(day.number, question) {
+                    eprintln!("{}", err);
+                }
+            }
+        }
+    }
+
+    match failures {
+        0 => Ok(()),
+        n => Err(anyhow!("{} answer(s) did not match the recorded answers", n)),
+    }
+}
+
+fn run(args: &Run) -> anyhow::Result<()> {
+    let (day, questions) = match args.target.as_slice() {
+        [day] => (day, vec![Question::One, Question::Two]),
+        [day, question] => (day, vec![question.parse::<Question>()?]),
+        _ => return Err(anyhow!("expected a day and an optional question, or --all")),
+    };
+
+    let day = find_day(day.parse()?)?;
+    let source = Source::from_args(args.input.as_deref(), args.example, day.number)?;
+
+    if let Some(path) = &args.dot {
+        let [question] = questions.as_slice() else {
+            return Err(anyhow!("--dot needs a question, since each question draws its own graph"));
+        };
+        day.write_dot(*question, &source, path)?;
+    }
+
+    if let Some(iterations) = args.bench {
+        return bench(&day, &source, iterations, &questions, args.strategy.as_deref());
+    }
+
+    for question in questions {
+        if args.explain {
+            eprintln!("{}", day.explanation(question, &source)?);
+        }
+
+        let outcome = day.run(question, &source, args.strategy.as_deref())?;
+        println!("{}", outcome.render(args.output));
+
+        if args.check {
+            Answers::load()?
+                .check(day.number, question, &source, &outcome.answer)
+                .into_result(day.number, question)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli: Cli = argh::from_env();
+    match cli.command {
+        Command::Run(args) if args.all && args.input.is_some() => {
+            Err(anyhow!("--input can't be used with --all"))
+        }
+        Command::Run(args) if args.all && args.strategy.is_some() => {
+            Err(anyhow!("--strategy can't be used with --all"))
+        }
+        Command::Run(args) if args.all && args.explain => {
+            Err(anyhow!("--explain can't be used with --all"))
+        }
+        Command::Run(args) if args.explain && args.bench.is_some() => {
+            Err(anyhow!("--explain can't be used with --bench"))
+        }
+        Command::Run(args) if args.all && args.dot.is_some() => {
+            Err(anyhow!("--dot can't be used with --all"))
+        }
+        Command::Run(args) if args.dot.is_some() && args.bench.is_some() => {
+            Err(anyhow!("--dot can't be used with --bench"))
+        }
+        Command::Run(args) if args.all => match args.bench {
+            Some(iterations) => bench_all(&args, iterations),
+            None => run_all(&args),
+        },
+        Command::Run(args) => run(&args),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crlf_files_give_the_same_answers() {
+        let path = std::env::temp_dir().join(format!("aoc-runner-crlf-{}.txt", std::process::id()));
+        for day in DAYS {
+            for question in [Question::One, Question::Two] {
+                let example = Source::Example.read(&day, question).unwrap();
+                std::fs::write(&path, example.replace('\n', "\r\n")).unwrap();
+                let crlf = day.run(question, &Source::File(path.clone()), None).unwrap();
+                let lf = day.run(question, &Source::Example, None).unwrap();
+                assert_eq!(crlf.answer, lf.answer, "day {} question {}", day.number, question);
+            }
+        }
+        std::fs::remove_file(&path).unwrap();
+    }
+}